@@ -0,0 +1,19 @@
+use std::process::ExitCode;
+
+use ace_parser::grammar::*;
+
+fn main() -> ExitCode {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from("examples/check_ace/broken.ace"));
+    let contents =
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("open file {path}: {e}"));
+
+    match ace(&contents).check(&path) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{:?}", miette::Report::new(error));
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -24,6 +24,15 @@ fn main() {
     let query = parse_tree.query(line, character);
     println!("query line: {line} character: {character}\n{:#?}", query);
     println!("show_completions: {:?}", query.show_completions());
+    println!("node: {:?}", query.node().map(|node| node.node_kind()));
+    println!(
+        "ancestors: {:?}",
+        query
+            .ancestors()
+            .iter()
+            .map(|node| node.node_kind())
+            .collect::<Vec<_>>()
+    );
 
     let line = 4;
     let character = 0;
@@ -0,0 +1,59 @@
+use lsp_types::{Position, PositionEncodingKind};
+
+/// An open text document, whose positions are converted between the encoding
+/// negotiated with the client and the byte offsets used by the parser.
+#[derive(Debug)]
+pub struct Document {
+    text: String,
+    encoding: PositionEncodingKind,
+}
+
+impl Document {
+    pub fn new(text: String, encoding: PositionEncodingKind) -> Self {
+        Document { text, encoding }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the line and the byte offset in the line of `position`, which is the
+    /// position expected by [`ace_parser::grammar::Ace::query`].
+    pub fn query_position(&self, position: Position) -> (u32, u32) {
+        let line = self
+            .text
+            .split_inclusive('\n')
+            .nth(position.line as usize)
+            .unwrap_or_default();
+        let mut units = 0;
+        let character = line
+            .char_indices()
+            .find(|(_, c)| {
+                units += self.units(*c);
+                units > position.character
+            })
+            .map_or(line.trim_end_matches(['\r', '\n']).len(), |(i, _)| i);
+        (position.line, character as u32)
+    }
+
+    /// Returns the position of the byte `offset` of the text.
+    pub fn position(&self, offset: usize) -> Position {
+        let before = &self.text[..offset.min(self.text.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Position {
+            line: before.matches('\n').count() as u32,
+            character: before[line_start..].chars().map(|c| self.units(c)).sum(),
+        }
+    }
+
+    /// Returns the number of code units of `c` in the negotiated encoding.
+    fn units(&self, c: char) -> u32 {
+        if self.encoding == PositionEncodingKind::UTF8 {
+            c.len_utf8() as u32
+        } else if self.encoding == PositionEncodingKind::UTF32 {
+            1
+        } else {
+            c.len_utf16() as u32
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! A language server of the **ACE** language speaking the Language Server Protocol
+//! over stdio. It offers completions from [`Query::show_completions`] and publishes
+//! the syntax errors and warnings of the open documents as diagnostics.
+//!
+//! [`Query::show_completions`]: ace_parser::grammar::Query::show_completions
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use ace_parser::grammar::ace;
+use ace_parser::grammar::diagnostic::AceError;
+use lsp_server::{Connection, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{Completion, Request as _};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    Diagnostic, DiagnosticSeverity, InitializeParams, InsertTextFormat, PositionEncodingKind,
+    PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
+    Uri,
+};
+
+mod document;
+
+use document::Document;
+
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let (id, params) = connection.initialize_start()?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+    let encoding = params
+        .capabilities
+        .general
+        .and_then(|general| general.position_encodings)
+        .filter(|encodings| encodings.contains(&PositionEncodingKind::UTF8))
+        .map_or(PositionEncodingKind::UTF16, |_| PositionEncodingKind::UTF8);
+    let capabilities = ServerCapabilities {
+        position_encoding: Some(encoding.clone()),
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions::default()),
+        ..Default::default()
+    };
+    connection.initialize_finish(
+        id,
+        serde_json::json!({
+            "capabilities": capabilities,
+            "serverInfo": { "name": "ace-lsp", "version": env!("CARGO_PKG_VERSION") },
+        }),
+    )?;
+
+    let server = Server {
+        connection,
+        encoding,
+        documents: HashMap::new(),
+    };
+    server.run()?;
+
+    io_threads.join()?;
+    Ok(())
+}
+
+struct Server {
+    connection: Connection,
+    encoding: PositionEncodingKind,
+    documents: HashMap<Uri, Document>,
+}
+
+impl Server {
+    /// Handles the messages of the client until it shuts the server down.
+    fn run(mut self) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let receiver = self.connection.receiver.clone();
+        for message in &receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let response = match request.method.as_str() {
+            Completion::METHOD => {
+                let id = request.id.clone();
+                match request.extract::<CompletionParams>(Completion::METHOD) {
+                    Ok((id, params)) => Response::new_ok(id, self.completion(params)),
+                    Err(error) => Response::new_err(
+                        id,
+                        lsp_server::ErrorCode::InvalidParams as i32,
+                        error.to_string(),
+                    ),
+                }
+            }
+            _ => Response::new_err(
+                request.id,
+                lsp_server::ErrorCode::MethodNotFound as i32,
+                format!("unsupported request `{}`", request.method),
+            ),
+        };
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Some(params) = notification_params::<DidOpenTextDocument>(notification) else {
+                    return Ok(());
+                };
+                let uri = params.text_document.uri;
+                let document = Document::new(params.text_document.text, self.encoding.clone());
+                self.documents.insert(uri.clone(), document);
+                self.publish_diagnostics(uri, Some(params.text_document.version))?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let Some(params) = notification_params::<DidChangeTextDocument>(notification)
+                else {
+                    return Ok(());
+                };
+                let uri = params.text_document.uri;
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    let document = Document::new(change.text, self.encoding.clone());
+                    self.documents.insert(uri.clone(), document);
+                }
+                self.publish_diagnostics(uri, Some(params.text_document.version))?;
+            }
+            DidCloseTextDocument::METHOD => {
+                let Some(params) = notification_params::<DidCloseTextDocument>(notification) else {
+                    return Ok(());
+                };
+                self.documents.remove(&params.text_document.uri);
+                self.send_notification::<PublishDiagnostics>(PublishDiagnosticsParams {
+                    uri: params.text_document.uri,
+                    diagnostics: vec![],
+                    version: None,
+                })?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the completions at the position of `params`. A completion containing
+    /// a `$` placeholder is a snippet, labelled with its first word.
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let (line, character) = document.query_position(position.position);
+        let items = ace(document.text())
+            .query(line, character)
+            .show_completions()
+            .into_iter()
+            .map(|completion| {
+                if completion.contains('$') {
+                    CompletionItem {
+                        label: snippet_label(completion),
+                        kind: Some(CompletionItemKind::SNIPPET),
+                        insert_text: Some(completion.to_string()),
+                        insert_text_format: Some(InsertTextFormat::SNIPPET),
+                        ..Default::default()
+                    }
+                } else {
+                    CompletionItem {
+                        label: completion.to_string(),
+                        kind: Some(CompletionItemKind::KEYWORD),
+                        ..Default::default()
+                    }
+                }
+            })
+            .collect();
+        Some(CompletionResponse::Array(items))
+    }
+
+    fn publish_diagnostics(
+        &self,
+        uri: Uri,
+        version: Option<i32>,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(());
+        };
+        let ace = ace(document.text());
+        let diagnostics = [
+            (ace.errors(), DiagnosticSeverity::ERROR),
+            (ace.warnings(), DiagnosticSeverity::WARNING),
+        ]
+        .into_iter()
+        .flat_map(|(errors, severity)| {
+            AceError::new(uri.as_str(), document.text(), errors)
+                .diagnostics()
+                .iter()
+                .filter_map(|diagnostic| {
+                    let span = diagnostic.labels().first()?;
+                    let message = match diagnostic.help() {
+                        Some(help) => format!("{}\nhelp: {help}", diagnostic.message()),
+                        None => diagnostic.message().clone(),
+                    };
+                    Some(Diagnostic {
+                        range: lsp_types::Range {
+                            start: document.position(span.offset()),
+                            end: document.position(span.offset() + span.len()),
+                        },
+                        severity: Some(severity),
+                        source: Some(String::from("ace-lsp")),
+                        message,
+                        ..Default::default()
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+        self.send_notification::<PublishDiagnostics>(PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version,
+        })
+    }
+
+    fn send_notification<N: lsp_types::notification::Notification>(
+        &self,
+        params: N::Params,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let notification = Notification::new(N::METHOD.to_string(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+}
+
+/// Returns the params of `notification`, or `None` if they are malformed. The client
+/// cannot be answered about a notification, so the error is only logged.
+fn notification_params<N: lsp_types::notification::Notification>(
+    notification: Notification,
+) -> Option<N::Params> {
+    notification
+        .extract(N::METHOD)
+        .inspect_err(|error| {
+            eprintln!(
+                "ace-lsp: ignoring the `{}` notification: {error}",
+                N::METHOD
+            )
+        })
+        .ok()
+}
+
+/// Returns the label of a snippet, which is its first word with the choices of a
+/// placeholder, e.g. `rom/ram` for `${1|rom,ram|} ${2} {`.
+fn snippet_label(snippet: &str) -> String {
+    let word = snippet.split_whitespace().next().unwrap_or_default();
+    match word
+        .strip_prefix("${")
+        .and_then(|word| word.split_once('|'))
+    {
+        Some((_, choices)) => choices.trim_end_matches(['|', '}']).replace(',', "/"),
+        None => word.to_string(),
+    }
+}
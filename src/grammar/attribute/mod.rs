@@ -1,200 +1,827 @@
 use super::*;
+use paste::paste;
 
+/// This macro generates the parse tree node of an attribute assignment
+/// `pragma = value;`. It creates the node struct, a parser function named after
+/// the snake case of the struct and the [`ParseTree`] implementation.
+///
+/// The pragma is parsed by the lexical terminal of the same name in [`token`], or
+/// by the one named after `=` when the struct name is followed by `= Pragma`, and
+/// the value by the given parser, stored in the field with the given name.
+///
+/// A [`Number`] value may be followed by `, "description" => check`, in which case
+/// a value rejected by `check` is reported as a syntax error expecting the described
+/// values.
+///
+/// An attribute may be set once in its statement, unless the struct name is preceded
+/// by `repeatable`, like the entries of a list which a statement may set many times.
+macro_rules! define_attribute {
+    (repeatable $struct_name:ident $(= $pragma:ident)?, $($rest:tt)+) => {
+        define_attribute!(@single false, $struct_name $(= $pragma)?, $($rest)+);
+    };
+    (@single $single:literal, $struct_name:ident, $($rest:tt)+) => {
+        define_attribute!(@single $single, $struct_name = $struct_name, $($rest)+);
+    };
+    (@single $single:literal, $struct_name:ident = $pragma:ident, $field:ident: $value:ty, $value_parser:expr $(, $expected:literal => $check:expr)?) => {
+        paste! {
+            #[doc = concat!(
+                " Represents the [`fn@", stringify!([<$struct_name:snake>]), "`] attribute of the **ACE** grammar.\n\n",
+                " ## Grammar\n\n",
+                " - [`fn@", stringify!([<$struct_name:snake>]), "`] <- [`token::", stringify!($pragma), "`] [`token::Equal`] ",
+                "`", stringify!($value_parser), "` [`token::Semicolon`]"
+            )]
+            #[derive(Debug, Getters)]
+            pub struct $struct_name<'a> {
+                range: Range,
+                #[getset(get = "pub")]
+                pragma: token::$pragma<'a>,
+                #[getset(get = "pub")]
+                equal: token::Equal<'a>,
+                #[getset(get = "pub")]
+                $field: $value,
+                #[getset(get = "pub")]
+                semicolon: recovery::Expected<token::Semicolon<'a>>,
+            }
+
+            #[doc = concat!(
+                " Parses the [`", stringify!($struct_name), "`] attribute."
+            )]
+            pub fn [<$struct_name:snake>]<'a>(
+                input: LocatedSpan<&'a str>,
+            ) -> IResult<LocatedSpan<&'a str>, $struct_name<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+                let (s, pragma) = token::[<$pragma:snake>]().parse(input)?;
+                let (s, equal) = token::equal().parse(s)?;
+                let (s, $field) = ($value_parser).parse(s)?;
+                let (s, semicolon) = recovery::expected(
+                    |s| {
+                        token::semicolon()
+                            .set_label_completion(marker::LabelCompletion::Attribute)
+                            .parse(s)
+                    },
+                    literal::token::SEMICOLON,
+                )
+                .parse(s)?;
+                let range = Range {
+                    start: input.into(),
+                    end: s.into(),
+                };
+                Ok((
+                    s,
+                    $struct_name {
+                        pragma,
+                        equal,
+                        $field,
+                        semicolon,
+                        range,
+                    },
+                ))
+            }
+
+            impl ParseTree for $struct_name<'_> {
+                fn node_kind(&self) -> NodeKind {
+                    NodeKind::Attribute(stringify!([<$struct_name:snake>]))
+                }
+
+                fn range(&self) -> Range {
+                    self.range
+                }
+
+                fn children(&self) -> Vec<&dyn ParseTree> {
+                    vec![&self.pragma, &self.equal, &self.$field, &self.semicolon]
+                }
+
+                fn keywords(&self) -> &'static [&'static str] {
+                    self.pragma.aliases()
+                }
+
+                fn is_single(&self) -> bool {
+                    $single
+                }
+
+                $(
+                    fn syntax_error(&self) -> Option<recovery::SyntaxError> {
+                        check_number(&self.$field, $expected, $check)
+                    }
+                )?
+            }
+        }
+    };
+    ($($rest:tt)+) => {
+        define_attribute!(@single true, $($rest)+);
+    };
+}
+
+define_attribute!(Name, identifier: token::Identifier<'a>, token::identifier());
+define_attribute!(
+    Vlen,
+    length: Number<'a>,
+    number,
+    "a power of two between 32 and 65536" => |v| v.is_power_of_two() && (32..=65536).contains(&v)
+);
+define_attribute!(
+    Dlen,
+    length: Number<'a>,
+    number,
+    "a power of two between 32 and 65536" => |v| v.is_power_of_two() && (32..=65536).contains(&v)
+);
+define_attribute!(Elen, length: Number<'a>, number, "32 or 64" => |v| v == 32 || v == 64);
+define_attribute!(Flen, length: Number<'a>, number);
+define_attribute!(Felen, length: Number<'a>, number);
+define_attribute!(StreamingPortWidth, width: Number<'a>, number);
+define_attribute!(
+    AddressBits,
+    width: Number<'a>,
+    number,
+    "a number between 1 and 64" => |v| (1..=64).contains(&v)
+);
+define_attribute!(Endian, value: Endianness<'a>, endianness);
+define_attribute!(March, isa: token::StringLiteral<'a>, token::string_literal());
+define_attribute!(OptimizationPolicy, policy: Policy<'a>, policy);
+define_attribute!(LmLatency, cycle: Number<'a>, number);
+define_attribute!(BusLatency, cycle: Number<'a>, number);
+define_attribute!(ExportLevelAhb, level: Number<'a>, number);
+define_attribute!(ExportLevelAxi, level: Number<'a>, number);
+define_attribute!(ExportLevelSram, level: Number<'a>, number);
+define_attribute!(ExportLevelPort, level: Number<'a>, number);
+define_attribute!(ExportLevelStreamingPort, level: Number<'a>, number);
+define_attribute!(RfBuffer, mode: BufferMode<'a>, buffer_mode);
+define_attribute!(GprBuffer, mode: BufferMode<'a>, buffer_mode);
+define_attribute!(FrfBuffer, mode: BufferMode<'a>, buffer_mode);
+define_attribute!(CustomErrorBits, width: Number<'a>, number);
+define_attribute!(CustomErrorEn, value: Boolean<'a>, boolean);
+define_attribute!(InsnQueue, value: Boolean<'a>, boolean);
+define_attribute!(GroupInBuffer, depth: Number<'a>, number);
+define_attribute!(GroupOutBuffer, depth: Number<'a>, number);
+define_attribute!(ErrorPc, value: Boolean<'a>, boolean);
+define_attribute!(ClockDomainCrossingStage, stage: Number<'a>, number);
+define_attribute!(TimeoutCycle, length: Number<'a>, number);
+define_attribute!(Width, length: Number<'a>, number);
+define_attribute!(Num, length: Number<'a>, number);
+define_attribute!(Reset, value: SignedNumber<'a>, signed_number);
+define_attribute!(ResetDefault, value: SignedNumber<'a>, signed_number);
+define_attribute!(Interface, value: BusInterface<'a>, bus_interface);
+define_attribute!(Latency, value: Number<'a>, number);
+define_attribute!(ByteEnable, value: Boolean<'a>, boolean);
+define_attribute!(WriteStrobe, value: Boolean<'a>, boolean);
+define_attribute!(MaxBurstLength, length: Number<'a>, number);
+define_attribute!(ErrorDetect, value: Boolean<'a>, boolean);
+define_attribute!(Content, value: token::Identifier<'a>, token::identifier());
+define_attribute!(ContentDefault, value: SignedNumber<'a>, signed_number);
+define_attribute!(IoType, value: Direction<'a>, direction);
+define_attribute!(ExportLevel, level: Number<'a>, number);
+define_attribute!(AccessType, value: AccessKind<'a>, access_kind);
+define_attribute!(
+    Operand,
+    operands: List<'a, operand::Operand<'a>>,
+    list(operand::operand, marker::LabelCompletion::Operand)
+);
+define_attribute!(
+    ImpliedOperand,
+    operands: List<'a, operand::Operand<'a>>,
+    list(operand::operand, marker::LabelCompletion::Operand)
+);
+define_attribute!(
+    CsrOperand,
+    operands: List<'a, operand::CsrOperand<'a>>,
+    list(operand::csr_operand, marker::LabelCompletion::None)
+);
+define_attribute!(
+    InsnEncode,
+    fields: List<'a, EncodingField<'a>>,
+    list(encoding_field, marker::LabelCompletion::None)
+);
+define_attribute!(Blocking, value: Boolean<'a>, boolean);
+define_attribute!(SideEffect, value: Boolean<'a>, boolean);
+define_attribute!(Csim, code: token::CodeBlock<'a>, token::code_block());
+define_attribute!(Chisel, code: token::CodeBlock<'a>, token::code_block());
+define_attribute!(Spinalhdl, code: token::CodeBlock<'a>, token::code_block());
+define_attribute!(
+    VectorOperand = Operand,
+    operands: List<'a, operand::VectorOperand<'a>>,
+    list(operand::vector_operand, marker::LabelCompletion::Operand)
+);
+define_attribute!(
+    VectorImpliedOperand = ImpliedOperand,
+    operands: List<'a, operand::VectorOperand<'a>>,
+    list(operand::vector_operand, marker::LabelCompletion::Operand)
+);
+define_attribute!(VectorMask, value: Boolean<'a>, boolean);
+define_attribute!(VectorUnit, value: token::Identifier<'a>, token::identifier());
+define_attribute!(RvvCustomKill, value: Boolean<'a>, boolean);
+define_attribute!(Utility, value: ExecutionMode<'a>, execution_mode);
+define_attribute!(UtilityKind, value: UtilityAccess<'a>, utility_access);
+define_attribute!(OutstandingInsnNum, length: Number<'a>, number);
+define_attribute!(Interrupt, value: Boolean<'a>, boolean);
+define_attribute!(
+    repeatable Sequence,
+    items: List<'a, SequenceItem<'a>>,
+    list(sequence_item, marker::LabelCompletion::SequenceItem)
+);
+define_attribute!(
+    repeatable SequenceList = List,
+    sequences: List<'a, token::Identifier<'a>>,
+    list(token::identifier(), marker::LabelCompletion::None)
+);
+define_attribute!(LoopType, kind: LoopKind<'a>, loop_kind);
+define_attribute!(Stride, length: Number<'a>, number);
+define_attribute!(TestSequenceInit, code: token::CodeBlock<'a>, token::code_block());
+define_attribute!(
+    repeatable InstructionList = List,
+    instructions: List<'a, token::Identifier<'a>>,
+    list(token::identifier(), marker::LabelCompletion::None)
+);
+define_attribute!(
+    repeatable Nbsync,
+    instructions: List<'a, token::Identifier<'a>>,
+    list(token::identifier(), marker::LabelCompletion::None)
+);
+define_attribute!(
+    repeatable Bsync,
+    instructions: List<'a, token::Identifier<'a>>,
+    list(token::identifier(), marker::LabelCompletion::None)
+);
+define_attribute!(StStatus, register: token::Identifier<'a>, token::identifier());
+define_attribute!(ErrorRd, value: Boolean<'a>, boolean);
+define_attribute!(Shared, value: Boolean<'a>, boolean);
+define_attribute!(HartId, id: Number<'a>, number);
+define_attribute!(repeatable MiscSetting, value: SettingValue<'a>, setting_value);
+
+define_choice!(
+    /// A numeric literal.
+    ///
+    /// - [`number`] <- [`token::HexNumber`] / [`token::BinNumber`] / [`token::SizedNumber`]
+    ///   / [`token::DecNumber`] / [`token::Zero`]
+    Number, pub fn number check number_error,
+    HexNumber(token::HexNumber<'a>) => token::hex_number(),
+    BinNumber(token::BinNumber<'a>) => token::bin_number(),
+    SizedNumber(token::SizedNumber<'a>) => token::sized_number(),
+    DecNumber(token::DecNumber<'a>) => token::dec_number(),
+    Zero(token::Zero<'a>) => token::zero(),
+);
+
+impl<'a> Number<'a> {
+    /// Returns the token of this number.
+    pub fn token(&self) -> &LocatedSpan<&'a str> {
+        match self {
+            Number::HexNumber(number) => number.token(),
+            Number::BinNumber(number) => number.token(),
+            Number::SizedNumber(number) => number.token(),
+            Number::DecNumber(number) => number.token(),
+            Number::Zero(number) => number.token(),
+        }
+    }
+
+    /// Returns the value of this number, e.g. `255` for `0xFF`, `0b11111111` or `8'hFF`.
+    ///
+    /// ```
+    /// # use ace_parser::grammar::attribute::{number, NumberError};
+    /// # use nom_locate::LocatedSpan;
+    /// let (_, n) = number(LocatedSpan::new("8'hFF")).unwrap();
+    /// assert_eq!(n.value(), Ok(255));
+    /// let (_, n) = number(LocatedSpan::new("4'hFF")).unwrap();
+    /// assert_eq!(n.value(), Err(NumberError::ExceedsSize(4)));
+    /// let (_, n) = number(LocatedSpan::new("340282366920938463463374607431768211456")).unwrap();
+    /// assert_eq!(n.value(), Err(NumberError::Overflow));
+    /// ```
+    pub fn value(&self) -> Result<u128, NumberError> {
+        match self {
+            Number::HexNumber(number) => digits_value(&number.token()[2..], 16),
+            Number::BinNumber(number) => digits_value(&number.token()[2..], 2),
+            Number::SizedNumber(number) => {
+                let value = digits_value(number.digits(), number.radix())?;
+                match number.size().parse::<u32>() {
+                    Ok(size) if size < u128::BITS && value >> size != 0 => {
+                        Err(NumberError::ExceedsSize(size))
+                    }
+                    _ => Ok(value),
+                }
+            }
+            Number::DecNumber(number) => digits_value(number.token(), 10),
+            Number::Zero(_) => Ok(0),
+        }
+    }
+}
+
+/// The error of the value of a [`Number`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, thiserror::Error)]
+pub enum NumberError {
+    #[error("number does not fit in 128 bits")]
+    Overflow,
+    #[error("number does not fit in its size of {0} bits")]
+    ExceedsSize(u32),
+}
+
+/// Returns the error of the value of `number`, if any.
+fn number_error(number: &Number) -> Option<recovery::SyntaxError> {
+    let error = number.value().err()?;
+    Some(value_error(*number.token(), error))
+}
+
+/// Returns the syntax error of the value of the number `token`.
+fn value_error(token: LocatedSpan<&str>, error: NumberError) -> recovery::SyntaxError {
+    let expected = match error {
+        NumberError::Overflow => String::from("a number of at most 128 bits"),
+        NumberError::ExceedsSize(size) => format!("a number of at most {size} bits"),
+    };
+    recovery::invalid_value(token, &expected, error.to_string())
+}
+
+/// Returns the value of `digits` in `radix`, ignoring the `_` separators.
+fn digits_value(digits: &str, radix: u32) -> Result<u128, NumberError> {
+    digits
+        .chars()
+        .filter(|c| *c != '_')
+        .try_fold(0u128, |value, c| {
+            let digit = c
+                .to_digit(radix)
+                .expect("digits should be checked by the grammar");
+            value.checked_mul(radix.into())?.checked_add(digit.into())
+        })
+        .ok_or(NumberError::Overflow)
+}
+
+/// Returns the error of `number` if its value is rejected by `check`, in which case
+/// the `expected` values are reported. A value which does not fit is reported by the
+/// [`Number`] node itself.
+fn check_number(
+    number: &Number,
+    expected: &str,
+    check: impl Fn(u128) -> bool,
+) -> Option<recovery::SyntaxError> {
+    let token = *number.token();
+    match number.value() {
+        Ok(value) if check(value) => None,
+        Ok(_) => Some(recovery::invalid_value(
+            token,
+            expected,
+            format!("expected {expected}, found `{}`", token.fragment()),
+        )),
+        Err(_) => None,
+    }
+}
+
+/// A numeric literal with an optional negative sign, e.g. `-1`, which is the value of
+/// the attributes holding the content of a register or a memory.
+///
+/// - [`signed_number`] <- [`token::NegativeSign`]? [`number`]
 #[derive(Debug, Getters)]
-pub struct Name<'a> {
+pub struct SignedNumber<'a> {
     range: Range,
     #[getset(get = "pub")]
-    pragma: token::Name<'a>,
-    #[getset(get = "pub")]
-    equal: token::Equal<'a>,
-    #[getset(get = "pub")]
-    identifier: token::Identifier<'a>,
+    negative_sign: Option<token::NegativeSign<'a>>,
     #[getset(get = "pub")]
-    semicolon: token::Semicolon<'a>,
+    number: Number<'a>,
 }
 
-pub fn name<'a>(
+pub fn signed_number<'a>(
     input: LocatedSpan<&'a str>,
-) -> IResult<LocatedSpan<&'a str>, Name<'a>, ErrorTree<LocatedSpan<&'a str>>> {
-    let (s, pragma) = token::name().parse(input)?;
-    let (s, equal) = token::equal().parse(s)?;
-    let (s, identifier) = token::identifier().parse(s)?;
-    let (s, semicolon) = token::semicolon()
-        .set_label_completion(marker::LabelCompletion::Attribute)
-        .parse(s)?;
+) -> IResult<LocatedSpan<&'a str>, SignedNumber<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+    let (s, negative_sign) = opt(token::negative_sign())(input)?;
+    let (s, number) = number(s)?;
     let range = Range {
         start: input.into(),
         end: s.into(),
     };
     Ok((
         s,
-        Name {
-            pragma,
-            equal,
-            identifier,
-            semicolon,
+        SignedNumber {
+            negative_sign,
+            number,
             range,
         },
     ))
 }
 
-impl ParseTree for Name<'_> {
+impl SignedNumber<'_> {
+    /// Returns the value of this number, e.g. `-1` for `-1`.
+    pub fn value(&self) -> Result<i128, NumberError> {
+        let magnitude = self.number.value()?;
+        match self.negative_sign {
+            Some(_) => 0i128.checked_sub_unsigned(magnitude),
+            None => i128::try_from(magnitude).ok(),
+        }
+        .ok_or(NumberError::Overflow)
+    }
+}
+
+impl ParseTree for SignedNumber<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Value("signed_number")
+    }
+
     fn range(&self) -> Range {
         self.range
     }
 
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-        if self.range().start <= pos && pos < self.range().end {
-            self.pragma
-                .query(pos)
-                .or_else(|_| self.equal.query(pos))
-                .or_else(|_| self.identifier.query(pos))
-                .or_else(|_| self.semicolon.query(pos))
-        } else {
-            Err(())
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        let mut children: Vec<&dyn ParseTree> = vec![];
+        if let Some(negative_sign) = &self.negative_sign {
+            children.push(negative_sign);
         }
+        children.push(&self.number);
+        children
+    }
+
+    /// Returns the error of a number whose magnitude fits in 128 bits but whose signed
+    /// value does not. The other errors are reported by the [`Number`] node.
+    fn syntax_error(&self) -> Option<recovery::SyntaxError> {
+        self.number.value().ok()?;
+        let error = self.value().err()?;
+        Some(value_error(*self.number.token(), error))
     }
 }
 
+define_choice!(
+    /// A `yes`/`no` switch.
+    ///
+    /// - [`boolean`] <- [`token::Yes`] / [`token::No`]
+    Boolean, pub fn boolean,
+    Yes(token::Yes<'a>) => token::yes(),
+    No(token::No<'a>) => token::no(),
+);
+
+define_choice!(
+    /// What the generated hardware is optimized for.
+    ///
+    /// - [`policy`] <- [`token::Frequency`] / [`token::Latency`]
+    Policy, pub fn policy,
+    Frequency(token::Frequency<'a>) => token::frequency(),
+    Latency(token::Latency<'a>) => token::latency(),
+);
+
+define_choice!(
+    /// How the operands of a register file are buffered.
+    ///
+    /// - [`buffer_mode`] <- [`token::Auto`] / [`token::Direct`]
+    BufferMode, pub fn buffer_mode,
+    Auto(token::Auto<'a>) => token::auto(),
+    Direct(token::Direct<'a>) => token::direct(),
+);
+
+define_choice!(
+    /// The byte order of a CPU.
+    ///
+    /// - [`endianness`] <- [`token::Big`] / [`token::Little`]
+    Endianness, pub fn endianness,
+    Big(token::Big<'a>) => token::big(),
+    Little(token::Little<'a>) => token::little(),
+);
+
+define_choice!(
+    /// A bus interface protocol.
+    ///
+    /// - [`bus_interface`] <- [`token::Sram`] / [`token::Ahb`] / [`token::Axi`]
+    BusInterface, pub fn bus_interface,
+    Sram(token::Sram<'a>) => token::sram(),
+    Ahb(token::Ahb<'a>) => token::ahb(),
+    Axi(token::Axi<'a>) => token::axi(),
+);
+
+define_choice!(
+    /// The direction of a port.
+    ///
+    /// - [`direction`] <- [`token::Input2`] / [`token::Output2`] / [`token::InOut2`]
+    ///   / [`token::Input`] / [`token::Output`] / [`token::InOut`]
+    Direction, pub fn direction,
+    In2(token::Input2<'a>) => token::input2(),
+    Out2(token::Output2<'a>) => token::output2(),
+    Io2(token::InOut2<'a>) => token::in_out2(),
+    In(token::Input<'a>) => token::input(),
+    Out(token::Output<'a>) => token::output(),
+    Io(token::InOut<'a>) => token::in_out(),
+);
+
+define_choice!(
+    /// The kind of access performed through a port.
+    ///
+    /// - [`access_kind`] <- [`token::Read`] / [`token::Write`] / [`token::Access`]
+    AccessKind, pub fn access_kind,
+    Read(token::Read<'a>) => token::read(),
+    Write(token::Write<'a>) => token::write(),
+    Access(token::Access<'a>) => token::access(),
+);
+
+define_choice!(
+    /// Whether an instruction runs in the background or in the foreground.
+    ///
+    /// - [`execution_mode`] <- [`token::Background`] / [`token::Foreground`]
+    ExecutionMode, pub fn execution_mode,
+    Background(token::Background<'a>) => token::background(),
+    Foreground(token::Foreground<'a>) => token::foreground(),
+);
+
+define_choice!(
+    /// What a utility instruction reads or writes.
+    ///
+    /// - [`utility_access`] <- [`token::RdUtil`] / [`token::WrUtil`] / [`token::RdInsn`]
+    ///   / [`token::WrInsn`]
+    UtilityAccess, pub fn utility_access,
+    RdUtil(token::RdUtil<'a>) => token::rd_util(),
+    WrUtil(token::WrUtil<'a>) => token::wr_util(),
+    RdInsn(token::RdInsn<'a>) => token::rd_insn(),
+    WrInsn(token::WrInsn<'a>) => token::wr_insn(),
+);
+
+define_choice!(
+    /// An item of a test sequence, which is an instruction or a synchronization point.
+    /// The markers are tried before the identifier, which they would otherwise match.
+    ///
+    /// - [`sequence_item`] <- [`token::AnyInsn`] / [`token::BgSync`] / [`token::SequenceSync`]
+    ///   / [`token::Identifier`]
+    SequenceItem, pub fn sequence_item,
+    AnyInsn(token::AnyInsn<'a>) => token::any_insn(),
+    BgSync(token::BgSync<'a>) => token::bg_sync(),
+    Sync(token::SequenceSync<'a>) => token::sequence_sync(),
+    Identifier(token::Identifier<'a>) => token::identifier(),
+);
+
+define_choice!(
+    /// How a test sequence is looped.
+    ///
+    /// - [`loop_kind`] <- [`token::Repeat`] / [`token::DoWhile`]
+    LoopKind, pub fn loop_kind,
+    Repeat(token::Repeat<'a>) => token::repeat(),
+    DoWhile(token::DoWhile<'a>) => token::do_while(),
+);
+
+define_choice!(
+    /// The value of a miscellaneous setting.
+    ///
+    /// - [`setting_value`] <- [`number`] / [`boolean`] / [`token::StringLiteral`]
+    ///   / [`token::Identifier`]
+    SettingValue, pub fn setting_value,
+    Number(Number<'a>) => number,
+    Boolean(Boolean<'a>) => boolean,
+    String(token::StringLiteral<'a>) => token::string_literal(),
+    Identifier(token::Identifier<'a>) => token::identifier(),
+);
+
+define_choice!(
+    /// A field of an instruction encoding.
+    ///
+    /// - [`encoding_field`] <- [`number`] / [`token::Identifier`]
+    EncodingField, pub fn encoding_field,
+    Number(Number<'a>) => number,
+    Identifier(token::Identifier<'a>) => token::identifier(),
+);
+
+/// A miscellaneous setting whose key is none of the known ones, e.g. the settings of
+/// older **ACE** files. It is kept in the parse tree and reported as a warning.
+///
+/// - [`setting`] <- [`token::Identifier`] [`token::Equal`] [`setting_value`] [`token::Semicolon`]
 #[derive(Debug, Getters)]
-pub struct Vlen<'a> {
+pub struct Setting<'a> {
     range: Range,
     #[getset(get = "pub")]
-    pragma: token::Vlen<'a>,
+    key: token::Identifier<'a>,
     #[getset(get = "pub")]
     equal: token::Equal<'a>,
     #[getset(get = "pub")]
-    length: Number<'a>,
+    value: SettingValue<'a>,
     #[getset(get = "pub")]
-    semicolon: token::Semicolon<'a>,
+    semicolon: recovery::Expected<token::Semicolon<'a>>,
 }
 
-pub fn vlen<'a>(
+pub fn setting<'a>(
     input: LocatedSpan<&'a str>,
-) -> IResult<LocatedSpan<&'a str>, Vlen<'a>, ErrorTree<LocatedSpan<&'a str>>> {
-    let (s, pragma) = token::vlen().parse(input)?;
+) -> IResult<LocatedSpan<&'a str>, Setting<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+    let (s, key) = token::identifier().parse(input)?;
     let (s, equal) = token::equal().parse(s)?;
-    let (s, length) = number(s)?;
-    let (s, semicolon) = token::semicolon()
-        .set_label_completion(marker::LabelCompletion::Attribute)
-        .parse(s)?;
+    let (s, value) = setting_value(s)?;
+    let (s, semicolon) = recovery::expected(
+        |s| {
+            token::semicolon()
+                .set_label_completion(marker::LabelCompletion::Attribute)
+                .parse(s)
+        },
+        literal::token::SEMICOLON,
+    )
+    .parse(s)?;
     let range = Range {
         start: input.into(),
         end: s.into(),
     };
     Ok((
         s,
-        Vlen {
-            pragma,
+        Setting {
+            key,
             equal,
-            length,
+            value,
             semicolon,
             range,
         },
     ))
 }
 
-impl ParseTree for Vlen<'_> {
-    fn range(&self) -> Range {
-        self.range
+impl ParseTree for Setting<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Attribute("setting")
     }
 
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-        if self.range().contains(pos) {
-            self.pragma
-                .query(pos)
-                .or_else(|_| self.equal.query(pos))
-                .or_else(|_| self.length.query(pos))
-                .or_else(|_| self.semicolon.query(pos))
-        } else {
-            Err(())
-        }
+    fn range(&self) -> Range {
+        self.range
     }
-}
-
-#[derive(Debug)]
-pub enum Number<'a> {
-    HexNumber(token::HexNumber<'a>),
-    DecNumber(token::DecNumber<'a>),
-}
-
-fn number<'a>(
-    input: LocatedSpan<&'a str>,
-) -> IResult<LocatedSpan<&'a str>, Number<'a>, ErrorTree<LocatedSpan<&'a str>>> {
-    alt((
-        token::hex_number().map(Number::HexNumber),
-        token::dec_number().map(Number::DecNumber),
-    ))(input)
-}
 
-impl ParseTree for Number<'_> {
-    fn range(&self) -> Range {
-        match self {
-            Number::HexNumber(hex_number) => hex_number.range(),
-            Number::DecNumber(dec_number) => dec_number.range(),
-        }
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        vec![&self.key, &self.equal, &self.value, &self.semicolon]
     }
 
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-        match self {
-            Number::HexNumber(hex_number) => hex_number.query(pos),
-            Number::DecNumber(dec_number) => dec_number.query(pos),
-        }
+    fn syntax_error(&self) -> Option<recovery::SyntaxError> {
+        Some(recovery::unknown_setting(
+            *self.key.token(),
+            &[literal::attribute::MISC_SETTING],
+        ))
     }
 }
 
+/// A comma separated list of items enclosed in braces, e.g. `{in gpr rs1, out gpr rd}`.
+/// An item which cannot be parsed is kept in an [`recovery::Error`] node, so that an
+/// unfinished list like `{in gpr rs1, }` is still completed.
+///
+/// - [`list`] <- [`token::LeftBrace`] (item ([`token::Comma`] item)*)? [`token::RightBrace`]
 #[derive(Debug, Getters)]
-pub struct TimeoutCycle<'a> {
+pub struct List<'a, T> {
     range: Range,
     #[getset(get = "pub")]
-    pragma: token::TimeoutCycle<'a>,
+    left_brace: token::LeftBrace<'a>,
     #[getset(get = "pub")]
-    equal: token::Equal<'a>,
+    items: Vec<recovery::Item<'a, T>>,
     #[getset(get = "pub")]
-    length: Number<'a>,
+    commas: Vec<token::Comma<'a>>,
     #[getset(get = "pub")]
-    semicolon: token::Semicolon<'a>,
+    right_brace: recovery::Expected<token::RightBrace<'a>>,
 }
 
-pub fn timeout_cycle<'a>(
-    input: LocatedSpan<&'a str>,
-) -> IResult<LocatedSpan<&'a str>, TimeoutCycle<'a>, ErrorTree<LocatedSpan<&'a str>>> {
-    let (s, pragma) = token::timeout_cycle().parse(input)?;
-    let (s, equal) = token::equal().parse(s)?;
-    let (s, length) = number(s)?;
-    let (s, semicolon) = token::semicolon()
-        .set_label_completion(marker::LabelCompletion::Attribute)
-        .parse(s)?;
-    let range = Range {
-        start: input.into(),
-        end: s.into(),
-    };
-    Ok((
-        s,
-        TimeoutCycle {
-            pragma,
-            equal,
-            length,
-            semicolon,
-            range,
-        },
-    ))
+/// Returns a parser of a [`List`] whose items are parsed by `item`. The left brace
+/// and the commas of the list, as well as the items which cannot be parsed, are given
+/// the `label_completion` of the first item.
+pub fn list<'a, T, P>(
+    mut item: P,
+    label_completion: marker::LabelCompletion,
+) -> impl Parser<LocatedSpan<&'a str>, List<'a, T>, ErrorTree<LocatedSpan<&'a str>>>
+where
+    P: Parser<LocatedSpan<&'a str>, T, ErrorTree<LocatedSpan<&'a str>>>,
+{
+    move |input: LocatedSpan<&'a str>| {
+        let (mut s, left_brace) = token::left_brace()
+            .set_label_completion(label_completion)
+            .parse(input)?;
+        let mut items = vec![];
+        let mut commas = vec![];
+        if !s.starts_with(literal::token::RIGHT_BRACE) {
+            loop {
+                let (rest, next) = recovery::item(|i| item.parse(i), label_completion).parse(s)?;
+                s = rest;
+                items.push(next);
+                let (rest, comma) = opt(|i| {
+                    token::comma()
+                        .set_label_completion(label_completion)
+                        .parse(i)
+                })(s)?;
+                let Some(comma) = comma else {
+                    break;
+                };
+                s = rest;
+                commas.push(comma);
+            }
+        }
+        let (s, right_brace) =
+            recovery::expected(token::right_brace(), literal::token::RIGHT_BRACE).parse(s)?;
+        let range = Range {
+            start: input.into(),
+            end: s.into(),
+        };
+        Ok((
+            s,
+            List {
+                left_brace,
+                items,
+                commas,
+                right_brace,
+                range,
+            },
+        ))
+    }
 }
 
-impl ParseTree for TimeoutCycle<'_> {
+impl<T: ParseTree> ParseTree for List<'_, T> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::List
+    }
+
     fn range(&self) -> Range {
         self.range
     }
 
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-        if self.range().contains(pos) {
-            self.pragma
-                .query(pos)
-                .or_else(|_| self.equal.query(pos))
-                .or_else(|_| self.length.query(pos))
-                .or_else(|_| self.semicolon.query(pos))
-        } else {
-            Err(())
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        let mut children: Vec<&dyn ParseTree> = vec![&self.left_brace];
+        for (i, item) in self.items.iter().enumerate() {
+            children.push(item);
+            if let Some(comma) = self.commas.get(i) {
+                children.push(comma);
+            }
+        }
+        children.push(&self.right_brace);
+        children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(source: &str) -> Result<u128, NumberError> {
+        let (rest, number) = number(LocatedSpan::new(source)).unwrap();
+        assert!(rest.is_empty(), "{source}");
+        number.value()
+    }
+
+    #[test]
+    fn number_values() {
+        assert_eq!(value("0xFF"), Ok(255));
+        assert_eq!(value("0Xdeadbeef"), Ok(0xdead_beef));
+        assert_eq!(value("0b1010"), Ok(10));
+        assert_eq!(value("0B11110000"), Ok(0xF0));
+        assert_eq!(value("32'hFF"), Ok(255));
+        assert_eq!(value("8'b1111_1111"), Ok(255));
+        assert_eq!(value("6'o77"), Ok(63));
+        assert_eq!(value("4'd15"), Ok(15));
+        assert_eq!(value("1234"), Ok(1234));
+        assert_eq!(value("0"), Ok(0));
+    }
+
+    #[test]
+    fn number_errors() {
+        assert_eq!(value("4'hFF"), Err(NumberError::ExceedsSize(4)));
+        assert_eq!(value("4'd16"), Err(NumberError::ExceedsSize(4)));
+        assert_eq!(value("128'hFF"), Ok(255));
+        assert_eq!(value("0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"), Ok(u128::MAX));
+        assert_eq!(
+            value("0x100000000000000000000000000000000"),
+            Err(NumberError::Overflow)
+        );
+        assert_eq!(
+            value("340282366920938463463374607431768211456"),
+            Err(NumberError::Overflow)
+        );
+    }
+
+    /// Returns the kind, the range and the message of each error of `source`.
+    fn errors(source: &str) -> Vec<(recovery::SyntaxErrorKind, Range, String)> {
+        ace(source)
+            .errors()
+            .iter()
+            .map(|error| (*error.kind(), *error.range(), error.message().clone()))
+            .collect()
+    }
+
+    fn range(line: u32, start: u32, end: u32) -> Range {
+        Range {
+            start: Position::new(line, start),
+            end: Position::new(line, end),
         }
     }
+
+    #[test]
+    fn every_number_attribute_is_validated() {
+        assert_eq!(
+            errors("cpu {\n    flen = 4'hFF;\n};\n"),
+            [(
+                recovery::SyntaxErrorKind::InvalidValue,
+                range(1, 11, 16),
+                String::from("number does not fit in its size of 4 bits")
+            )]
+        );
+        assert_eq!(
+            errors("cpu {\n    vlen = 0x100000000000000000000000000000000;\n};\n"),
+            [(
+                recovery::SyntaxErrorKind::InvalidValue,
+                range(1, 11, 46),
+                String::from("number does not fit in 128 bits")
+            )]
+        );
+        assert_eq!(errors("cpu {\n    flen = 32'hFF;\n};\n"), []);
+    }
+
+    #[test]
+    fn signed_number_attributes_are_validated() {
+        let reset = |value: &str| errors(&format!("reg r {{\n    reset = {value};\n}};\n"));
+        assert_eq!(reset("-0x80000000000000000000000000000000"), []);
+        assert_eq!(
+            reset("0x80000000000000000000000000000000"),
+            [(
+                recovery::SyntaxErrorKind::InvalidValue,
+                range(1, 12, 46),
+                String::from("number does not fit in 128 bits")
+            )]
+        );
+        assert_eq!(
+            reset("-8'h1FF"),
+            [(
+                recovery::SyntaxErrorKind::InvalidValue,
+                range(1, 13, 19),
+                String::from("number does not fit in its size of 8 bits")
+            )]
+        );
+    }
 }
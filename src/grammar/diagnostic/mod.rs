@@ -0,0 +1,149 @@
+use super::*;
+use miette::{Diagnostic, LabeledSpan, NamedSource, SourceSpan};
+use thiserror::Error;
+
+/// The syntax errors of an **ACE** file, reported together with its source so that
+/// [`miette`] renders each of them with the labelled lines they concern.
+///
+/// ```
+/// # use ace_parser::grammar::ace;
+/// let source = "cpu {\n    vlen = 512\n};\n";
+/// let error = ace(source).check("cpu.ace").unwrap_err();
+/// assert_eq!(error.diagnostics()[0].message(), "expected `;` after `vlen = 512`");
+/// ```
+#[derive(Debug, Error, Diagnostic, Getters)]
+#[error("could not parse `{}` due to {} syntax error(s)", .src.name(), .diagnostics.len())]
+pub struct AceError {
+    #[source_code]
+    src: NamedSource<String>,
+    #[related]
+    #[getset(get = "pub")]
+    diagnostics: Vec<SyntaxDiagnostic>,
+}
+
+impl AceError {
+    /// Creates the report of `errors`, which are found in the file `name` whose content is `source`.
+    pub fn new(name: impl AsRef<str>, source: &str, errors: &[recovery::SyntaxError]) -> Self {
+        AceError {
+            src: NamedSource::new(name, source.to_string()),
+            diagnostics: errors
+                .iter()
+                .map(|error| SyntaxDiagnostic::new(source, error))
+                .collect(),
+        }
+    }
+}
+
+/// A single syntax error, with a message naming the expected tokens, a label on the
+/// offending input and a help text.
+#[derive(Debug, Error, Diagnostic, Getters)]
+#[error("{message}")]
+pub struct SyntaxDiagnostic {
+    #[getset(get = "pub")]
+    message: String,
+    #[label(collection)]
+    #[getset(get = "pub")]
+    labels: Vec<LabeledSpan>,
+    #[help]
+    #[getset(get = "pub")]
+    help: Option<String>,
+}
+
+/// The number of expected tokens above which they are listed in the help text
+/// instead of the message.
+const MAX_EXPECTED_IN_MESSAGE: usize = 6;
+
+impl SyntaxDiagnostic {
+    fn new(source: &str, error: &recovery::SyntaxError) -> Self {
+        match (error.kind(), error.found()) {
+            (recovery::SyntaxErrorKind::InvalidValue, _) => Self::invalid_value(error),
+            (recovery::SyntaxErrorKind::UnknownSetting, _) => Self::unknown_setting(error),
+            (_, None) => Self::missing(source, error),
+            (_, Some(found)) => Self::unexpected(source, error, found),
+        }
+    }
+
+    /// A value is not allowed: the label tells which values are.
+    fn invalid_value(error: &recovery::SyntaxError) -> Self {
+        SyntaxDiagnostic {
+            message: error.message().clone(),
+            labels: vec![LabeledSpan::new_primary_with_span(
+                Some(format!("expected {}", error.expected().join(" or "))),
+                *error.location(),
+            )],
+            help: None,
+        }
+    }
+
+    /// A setting has an unknown key: the help text lists the known ones.
+    fn unknown_setting(error: &recovery::SyntaxError) -> Self {
+        SyntaxDiagnostic {
+            message: error.message().clone(),
+            labels: vec![LabeledSpan::new_primary_with_span(
+                Some(String::from("unknown setting")),
+                *error.location(),
+            )],
+            help: Some(format!(
+                "the setting is kept unchecked, the known ones are {}",
+                error.expected().join(", ")
+            )),
+        }
+    }
+
+    /// A token is missing: the message names the input it should follow, and the
+    /// label points right after that input.
+    fn missing(source: &str, error: &recovery::SyntaxError) -> Self {
+        let expected = error.expected().join(" or ");
+        let before = source[..error.location().offset()].trim_end();
+        let line = before.lines().last().unwrap_or_default().trim();
+        let statement = line
+            .rfind([';', '{', '}'])
+            .map(|end| line[end + 1..].trim())
+            .filter(|statement| !statement.is_empty())
+            .unwrap_or(line);
+        let message = if statement.is_empty() {
+            format!("expected {expected}")
+        } else {
+            format!("expected {expected} after `{statement}`")
+        };
+        SyntaxDiagnostic {
+            message,
+            labels: vec![LabeledSpan::new_primary_with_span(
+                Some(format!("expected {expected}")),
+                SourceSpan::from((before.len(), 0)),
+            )],
+            help: Some(format!("add {expected} here")),
+        }
+    }
+
+    /// The input cannot be derived: the message names the tokens expected where the
+    /// parser fails, and the help text tells which input is skipped.
+    fn unexpected(source: &str, error: &recovery::SyntaxError, found: &str) -> Self {
+        let expected = error.expected();
+        let (message, help) = if expected.len() > MAX_EXPECTED_IN_MESSAGE {
+            (
+                format!(
+                    "expected one of {} possible tokens, found {found}",
+                    expected.len()
+                ),
+                format!("expected one of {}", expected.join(", ")),
+            )
+        } else {
+            let skipped =
+                &source[error.span().offset()..error.span().offset() + error.span().len()];
+            let skipped = skipped.lines().next().unwrap_or_default().trim();
+            (
+                error.message().clone(),
+                format!("`{skipped}` is skipped to resume parsing"),
+            )
+        };
+        SyntaxDiagnostic {
+            message,
+            labels: vec![LabeledSpan::new_primary_with_span(
+                Some(format!("unexpected {found}")),
+                *error.location(),
+            )],
+            help: Some(help),
+        }
+    }
+}
@@ -1,9 +1,8 @@
 use super::*;
 
 use nom::character::complete::multispace1;
-use nom::combinator::eof;
 use nom::Parser;
-use nom::{branch::alt, bytes::complete::take_while, combinator::opt, multi::many0, IResult};
+use nom::{branch::alt, bytes::complete::take_while, combinator::opt, IResult};
 
 use nom_supreme::error::ErrorTree;
 use nom_supreme::tag::complete::{tag, tag_no_case};
@@ -12,59 +11,154 @@ use nom_supreme::ParserExt;
 use getset::Getters;
 use nom_locate::LocatedSpan;
 
+/// Parses an **ACE** file.
+///
+/// The parser recovers from syntax errors: the parts of the input it cannot derive
+/// are kept as [`recovery::Error`] nodes, the tokens it expects but does not find
+/// as [`recovery::Missing`] nodes, and every one of them is reported in
+/// [`Ace::errors`]. The findings which do not prevent the file from being loaded, like
+/// an unknown setting, are reported in [`Ace::warnings`] instead.
 pub fn ace<'a>(input: &'a str) -> Ace<'a> {
-    let (_, statements) = many0(statement)
-        .parse(input.into())
-        .expect("parser should not fail.");
-    Ace { statements }
+    let (s, spacing) = token::split_spacing(input.into());
+    let statements = recovery::statements(statement, Statement::Error)(s);
+    let mut ace = Ace {
+        source: input,
+        spacing,
+        statements,
+        errors: vec![],
+        warnings: vec![],
+    };
+    let mut errors: Vec<_> = recovery::unterminated_comment(&ace.spacing)
+        .into_iter()
+        .collect();
+    ace.statements
+        .iter()
+        .for_each(|s| collect_syntax_errors(s, &mut errors));
+    (ace.warnings, ace.errors) = errors
+        .into_iter()
+        .partition(recovery::SyntaxError::is_warning);
+    ace
 }
 
 #[derive(Debug, Getters)]
 pub struct Ace<'a> {
+    /// The parsed source.
+    #[getset(get = "pub")]
+    source: &'a str,
+    /// Any spacing and comments found before the first statement.
+    #[getset(get = "pub")]
+    spacing: Vec<LocatedSpan<&'a str>>,
     #[getset(get = "pub")]
     statements: Vec<Statement<'a>>,
+    /// The syntax errors found in the file, in the order of their position.
+    #[getset(get = "pub")]
+    errors: Vec<recovery::SyntaxError>,
+    /// The warnings found in the file, in the order of their position.
+    #[getset(get = "pub")]
+    warnings: Vec<recovery::SyntaxError>,
+}
+
+/// Appends the syntax errors of `node` and of its descendants to `errors`.
+fn collect_syntax_errors(node: &dyn ParseTree, errors: &mut Vec<recovery::SyntaxError>) {
+    errors.extend(node.syntax_error());
+    node.children()
+        .into_iter()
+        .for_each(|child| collect_syntax_errors(child, errors));
 }
 
 impl<'a> Ace<'a> {
+    /// Returns the report of the syntax errors found in the file, named `name` in it,
+    /// or `Ok(())` if there is none.
+    pub fn check(&self, name: impl AsRef<str>) -> Result<(), diagnostic::AceError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(diagnostic::AceError::new(name, self.source, &self.errors))
+        }
+    }
+
+    /// Queries the node at the zero-based `line` and `character` offset of the file.
     pub fn query<'b>(&'b self, line: u32, character: u32) -> Query<'b> {
         let pos = Position::new(line, character);
         Query {
-            query_result: self
+            path: self
                 .statements
                 .iter()
-                .find_map(|s| {
-                    if s.range().contains(pos) {
-                        s.query(pos).ok()
-                    } else {
-                        None
-                    }
-                })
-                .ok_or(()),
+                .find_map(|s| path(s, pos))
+                .unwrap_or_default(),
         }
     }
 }
 
+/// Returns the nodes from `node` down to the leaf node containing `pos`, or `None`
+/// if no leaf node under `node` contains it.
+fn path(node: &dyn ParseTree, pos: Position) -> Option<Vec<&dyn ParseTree>> {
+    if !node.range().contains(pos) {
+        return None;
+    }
+    let children = node.children();
+    if children.is_empty() {
+        return Some(vec![node]);
+    }
+    let mut path = children.into_iter().find_map(|child| path(child, pos))?;
+    path.insert(0, node);
+    Some(path)
+}
+
+/// The result of [`Ace::query`]: the leaf node found at the queried position and its
+/// ancestors, or nothing if the position is past the last statement.
 #[derive(Debug)]
 pub struct Query<'b> {
-    query_result: Result<&'b dyn ParseTree, ()>,
+    path: Vec<&'b dyn ParseTree>,
 }
 
 impl<'b> Query<'b> {
+    /// Returns the leaf node found at the queried position.
+    pub fn node(&self) -> Option<&'b dyn ParseTree> {
+        self.path.last().copied()
+    }
+
+    /// Returns the ancestors of the found node, from the top-level statement down to
+    /// the parent of the node.
+    pub fn ancestors(&self) -> &[&'b dyn ParseTree] {
+        &self.path[..self.path.len().saturating_sub(1)]
+    }
+
+    /// Returns the completions at the queried position. The label completion of the
+    /// found node is scoped by each of its ancestors, from the innermost one, and the
+    /// completions excluded by any of them are removed, e.g. the attributes already set
+    /// in the enclosing statement which may be set only once.
     pub fn show_completions(&self) -> Vec<&'static str> {
-        self.query_result
-            .as_ref()
-            .map(|t| t.show_completions())
-            .unwrap_or_else(|_| {
-                let m = marker::LabelCompletion::Statement;
-                m.completion()
-            }) // Err case means eof. So, show completion for statements.
+        match self.node() {
+            Some(node) => {
+                let label = self
+                    .ancestors()
+                    .iter()
+                    .rev()
+                    .fold(node.label_completion(), |label, ancestor| {
+                        ancestor.scope_label_completion(label)
+                    });
+                let excluded: Vec<_> = self
+                    .ancestors()
+                    .iter()
+                    .flat_map(|ancestor| ancestor.excluded_completions(label))
+                    .collect();
+                label
+                    .completion()
+                    .into_iter()
+                    .filter(|completion| !excluded.contains(completion))
+                    .collect()
+            }
+            // None means eof. So, show completion for statements.
+            None => marker::LabelCompletion::Statement.completion(),
+        }
     }
 }
 
 /// Position in a text document expressed as zero-based line and character offset.
 /// A position is between two characters like an 'insert' cursor in a editor.
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Default)]
-struct Position {
+pub struct Position {
     /// Line position in a document (zero-based).
     pub line: u32,
     /// Character offset on a line in a document (zero-based). The meaning of this
@@ -92,7 +186,7 @@ impl From<LocatedSpan<&str>> for Position {
 /// A range in a text document expressed as (zero-based) start and end positions.
 /// A range is comparable to a selection in an editor. Therefore the end position is exclusive.
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
-struct Range {
+pub struct Range {
     /// The range's start position.
     pub start: Position,
     /// The range's end position.
@@ -105,44 +199,215 @@ impl Range {
     }
 }
 
-trait ParseTree: std::fmt::Debug {
+/// The kind of a node of the parse tree, with the name of the grammar rule deriving it,
+/// e.g. `NodeKind::Attribute("vlen")` for a [`attribute::Vlen`] node.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum NodeKind {
+    Statement(&'static str),
+    Attribute(&'static str),
+    Operand(&'static str),
+    List,
+    Value(&'static str),
+    Token(&'static str),
+    Error,
+    Missing,
+}
+
+/// A node of the parse tree.
+///
+/// The nodes of an ordered choice, like [`Statement`], are transparent: they delegate
+/// every method to the node of their matched alternative.
+pub trait ParseTree: std::fmt::Debug {
+    fn node_kind(&self) -> NodeKind;
     fn range(&self) -> Range;
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()>;
+    /// Returns the label completion of this node, which tells what can be completed at it.
+    fn label_completion(&self) -> marker::LabelCompletion {
+        marker::LabelCompletion::None
+    }
+    /// Returns the label completion of a descendant node in the scope of this node, e.g.
+    /// a statement restricts the attributes completed in it to its legal attributes.
+    fn scope_label_completion(&self, label: marker::LabelCompletion) -> marker::LabelCompletion {
+        label
+    }
+    /// Returns the completions excluded from the `label` completion of a descendant node
+    /// in the scope of this node, e.g. a statement excludes the single attributes already set
+    /// in it, see [`ParseTree::is_single`].
+    fn excluded_completions(&self, _label: marker::LabelCompletion) -> Vec<&'static str> {
+        vec![]
+    }
+    /// Returns the spellings of the keyword introducing this node, e.g. `["sequence", "seq"]`
+    /// for an [`attribute::Sequence`] node.
+    fn keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// Returns whether this node is an attribute which may be set only once in its
+    /// statement.
+    fn is_single(&self) -> bool {
+        false
+    }
     fn show_completions(&self) -> Vec<&'static str> {
+        self.label_completion().completion()
+    }
+    /// Returns the child nodes in the order of their position.
+    fn children(&self) -> Vec<&dyn ParseTree> {
         vec![]
     }
+    /// Returns the syntax error this node stands for, if it is an error node.
+    fn syntax_error(&self) -> Option<recovery::SyntaxError> {
+        None
+    }
 }
 
-#[derive(Debug)]
-pub enum Statement<'a> {
-    Cpu(statement::Cpu<'a>),
-    Config(statement::Config<'a>),
-}
-
-fn statement<'a>(
-    input: LocatedSpan<&'a str>,
-) -> IResult<LocatedSpan<&'a str>, Statement<'a>, ErrorTree<LocatedSpan<&'a str>>> {
-    alt((
-        statement::cpu.map(Statement::Cpu),
-        statement::config.map(Statement::Config),
-    ))(input)
-}
+/// This macro generates an enum node whose variants are the alternative
+/// derivations of an ordered choice (`e1 / e2 / ...`) of the **ACE** grammar.
+/// It creates the enum, a parser function trying each derivation in the given
+/// order, and the [`ParseTree`] implementation delegating to the matched variant.
+///
+/// With `else Variant` after the parser function, the enum gets one more variant
+/// holding the [`recovery::Error`] node which a recovering parser produces in
+/// place of the input no derivation matches.
+///
+/// With `check f` after the parser function, the syntax error returned by `f` for a
+/// node derived by any alternative, e.g. a number out of range, is reported before
+/// the one of the alternative.
+macro_rules! define_choice {
+    (
+        $(#[$meta:meta])*
+        $enum_name:ident, $fn_vis:vis fn $parser_name:ident $(else $error_variant:ident)?
+        $(check $check:expr)?,
+        $($variant:ident($node:ty) => $parser:expr),+ $(,)?
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        #[allow(clippy::large_enum_variant)]
+        pub enum $enum_name<'a> {
+            $($variant($node),)+
+            $($error_variant(recovery::Error<'a>),)?
+        }
 
-impl ParseTree for Statement<'_> {
-    fn range(&self) -> Range {
-        match self {
-            Statement::Cpu(cpu) => cpu.range(),
-            Statement::Config(config) => config.range(),
+        $fn_vis fn $parser_name<'a>(
+            input: LocatedSpan<&'a str>,
+        ) -> IResult<LocatedSpan<&'a str>, $enum_name<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+            define_choice!(@alt $enum_name; $($variant => $parser),+).parse(input)
         }
-    }
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-        match self {
-            Statement::Cpu(cpu) => cpu.query(pos),
-            Statement::Config(config) => config.query(pos),
+
+        impl ParseTree for $enum_name<'_> {
+            fn node_kind(&self) -> NodeKind {
+                match self {
+                    $($enum_name::$variant(node) => node.node_kind(),)+
+                    $($enum_name::$error_variant(node) => node.node_kind(),)?
+                }
+            }
+
+            fn range(&self) -> Range {
+                match self {
+                    $($enum_name::$variant(node) => node.range(),)+
+                    $($enum_name::$error_variant(node) => node.range(),)?
+                }
+            }
+
+            fn label_completion(&self) -> marker::LabelCompletion {
+                match self {
+                    $($enum_name::$variant(node) => node.label_completion(),)+
+                    $($enum_name::$error_variant(node) => node.label_completion(),)?
+                }
+            }
+
+            fn scope_label_completion(&self, label: marker::LabelCompletion) -> marker::LabelCompletion {
+                match self {
+                    $($enum_name::$variant(node) => node.scope_label_completion(label),)+
+                    $($enum_name::$error_variant(node) => node.scope_label_completion(label),)?
+                }
+            }
+
+            fn excluded_completions(&self, label: marker::LabelCompletion) -> Vec<&'static str> {
+                match self {
+                    $($enum_name::$variant(node) => node.excluded_completions(label),)+
+                    $($enum_name::$error_variant(node) => node.excluded_completions(label),)?
+                }
+            }
+
+            fn keywords(&self) -> &'static [&'static str] {
+                match self {
+                    $($enum_name::$variant(node) => node.keywords(),)+
+                    $($enum_name::$error_variant(node) => node.keywords(),)?
+                }
+            }
+
+            fn is_single(&self) -> bool {
+                match self {
+                    $($enum_name::$variant(node) => node.is_single(),)+
+                    $($enum_name::$error_variant(node) => node.is_single(),)?
+                }
+            }
+
+            fn children(&self) -> Vec<&dyn ParseTree> {
+                match self {
+                    $($enum_name::$variant(node) => node.children(),)+
+                    $($enum_name::$error_variant(node) => node.children(),)?
+                }
+            }
+
+            fn syntax_error(&self) -> Option<recovery::SyntaxError> {
+                $(
+                    if let Some(error) = ($check)(self) {
+                        return Some(error);
+                    }
+                )?
+                match self {
+                    $($enum_name::$variant(node) => node.syntax_error(),)+
+                    $($enum_name::$error_variant(node) => node.syntax_error(),)?
+                }
+            }
         }
-    }
+    };
+    (@alt $enum_name:ident; $variant:ident => $parser:expr) => {
+        ($parser).map($enum_name::$variant)
+    };
+    (@alt $enum_name:ident; $variant:ident => $parser:expr, $($rest:tt)+) => {
+        ($parser)
+            .map($enum_name::$variant)
+            .or(define_choice!(@alt $enum_name; $($rest)+))
+    };
 }
 
+define_choice!(
+    /// A top-level **ACE** statement.
+    ///
+    /// - [`statement`] <- [`statement::cpu`] / [`statement::config`] / [`statement::acr`]
+    ///   / [`statement::acm`] / [`statement::acp`] / [`statement::instruction`]
+    ///   / [`statement::vector_instruction`] / [`statement::rvv_instruction`]
+    ///   / [`statement::background_instruction`] / [`statement::utility_instruction`]
+    ///   / [`statement::csim_header`] / [`statement::include`] / [`statement::test_pattern`]
+    ///   / [`statement::test_sequence`] / [`statement::testbench_sequence`]
+    ///   / [`statement::insn_group`] / [`statement::sync`] / [`statement::status`]
+    ///   / [`statement::resource`] / [`statement::acl_miscellaneous_setting`]
+    Statement, fn statement else Error,
+    Cpu(statement::Cpu<'a>) => statement::cpu,
+    Config(statement::Config<'a>) => statement::config,
+    Acr(statement::Acr<'a>) => statement::acr,
+    Acm(statement::Acm<'a>) => statement::acm,
+    Acp(statement::Acp<'a>) => statement::acp,
+    Instruction(statement::Instruction<'a>) => statement::instruction,
+    VectorInstruction(statement::VectorInstruction<'a>) => statement::vector_instruction,
+    RvvInstruction(statement::RvvInstruction<'a>) => statement::rvv_instruction,
+    BackgroundInstruction(statement::BackgroundInstruction<'a>) => statement::background_instruction,
+    UtilityInstruction(statement::UtilityInstruction<'a>) => statement::utility_instruction,
+    CsimHeader(statement::CsimHeader<'a>) => statement::csim_header,
+    Include(statement::Include<'a>) => statement::include,
+    TestPattern(statement::TestPattern<'a>) => statement::test_pattern,
+    TestSequence(statement::TestSequence<'a>) => statement::test_sequence,
+    TestbenchSequence(statement::TestbenchSequence<'a>) => statement::testbench_sequence,
+    InsnGroup(statement::InsnGroup<'a>) => statement::insn_group,
+    Sync(statement::Sync<'a>) => statement::sync,
+    Status(statement::Status<'a>) => statement::status,
+    Resource(statement::Resource<'a>) => statement::resource,
+    AclMiscellaneousSetting(statement::AclMiscellaneousSetting<'a>) => statement::acl_miscellaneous_setting,
+);
+
 pub mod attribute;
+pub mod diagnostic;
+pub mod operand;
+pub mod recovery;
 pub mod statement;
 pub mod token;
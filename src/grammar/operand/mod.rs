@@ -0,0 +1,465 @@
+use super::*;
+
+define_choice!(
+    /// An operand declaration in the list of the `operand` or `implied_operand`
+    /// attribute of an instruction.
+    ///
+    /// - [`fn@operand`] <- [`register_operand`] / [`immediate_operand`] / [`function_operand`]
+    Operand, pub fn operand,
+    Register(RegisterOperand<'a>) => register_operand,
+    Immediate(ImmediateOperand<'a>) => immediate_operand,
+    Function(FunctionOperand<'a>) => function_operand,
+);
+
+define_choice!(
+    /// An operand declaration in the list of the `operand` or `implied_operand`
+    /// attribute of a vector instruction, whose elements may be twice as wide as
+    /// the ones of the other operands.
+    ///
+    /// - [`vector_operand`] <- [`vector_register_operand`] / [`immediate_operand`]
+    ///   / [`function_operand`]
+    VectorOperand, pub fn vector_operand,
+    Register(RegisterOperand<'a, VectorElementType<'a>>) => vector_register_operand,
+    Immediate(ImmediateOperand<'a>) => immediate_operand,
+    Function(FunctionOperand<'a>) => function_operand,
+);
+
+/// An operand stored in a register file, a memory or a custom storage (ACR or ACM),
+/// e.g. `in gpr rs1` or `out mem32 uint dst`. The type `E` is the element type of
+/// the operand, which is a [`VectorElementType`] for the operands of a vector instruction.
+/// Once its direction is parsed, a missing storage or identifier is a [`recovery::Missing`]
+/// node, so that an unfinished operand like `in gpr` is still completed.
+///
+/// - [`register_operand`] <- [`direction`] [`storage`] [`element_type`]? [`token::Identifier`]
+/// - [`vector_register_operand`] <- [`direction`] [`storage`] [`vector_element_type`]?
+///   [`token::Identifier`]
+#[derive(Debug, Getters)]
+pub struct RegisterOperand<'a, E = ElementType<'a>> {
+    range: Range,
+    #[getset(get = "pub")]
+    direction: Direction<'a>,
+    #[getset(get = "pub")]
+    storage: recovery::Expected<Storage<'a>>,
+    #[getset(get = "pub")]
+    element_type: Option<E>,
+    #[getset(get = "pub")]
+    identifier: recovery::Expected<token::Identifier<'a>>,
+}
+
+pub fn register_operand<'a>(
+    input: LocatedSpan<&'a str>,
+) -> IResult<LocatedSpan<&'a str>, RegisterOperand<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+    register_operand_of(element_type).parse(input)
+}
+
+pub fn vector_register_operand<'a>(
+    input: LocatedSpan<&'a str>,
+) -> IResult<
+    LocatedSpan<&'a str>,
+    RegisterOperand<'a, VectorElementType<'a>>,
+    ErrorTree<LocatedSpan<&'a str>>,
+> {
+    register_operand_of(vector_element_type).parse(input)
+}
+
+/// Returns a parser of a [`RegisterOperand`] whose element type is parsed by `element_type`.
+fn register_operand_of<'a, E, P>(
+    mut element_type: P,
+) -> impl Parser<LocatedSpan<&'a str>, RegisterOperand<'a, E>, ErrorTree<LocatedSpan<&'a str>>>
+where
+    P: Parser<LocatedSpan<&'a str>, E, ErrorTree<LocatedSpan<&'a str>>>,
+{
+    move |input: LocatedSpan<&'a str>| {
+        let (s, direction) = direction(input)?;
+        let (s, storage) = recovery::expected_named(storage, "storage").parse(s)?;
+        let (s, element_type) = opt(|s| element_type.parse(s))(s)?;
+        let (s, identifier) =
+            recovery::expected_named(token::identifier(), "identifier").parse(s)?;
+        let range = Range {
+            start: input.into(),
+            end: s.into(),
+        };
+        Ok((
+            s,
+            RegisterOperand {
+                direction,
+                storage,
+                element_type,
+                identifier,
+                range,
+            },
+        ))
+    }
+}
+
+impl<E> RegisterOperand<'_, E> {
+    /// Returns the width in bits of a memory operand, e.g. `32` for `mem32`,
+    /// or `None` for the other storages.
+    pub fn width(&self) -> Option<u32> {
+        match self.storage.found() {
+            Some(Storage::Memory(memory)) => Some(keyword_width(memory.token())),
+            _ => None,
+        }
+    }
+}
+
+/// The element type of a [`RegisterOperand`], whose spellings are completed after
+/// the storage of the operand.
+pub trait OperandElementType: ParseTree {
+    const LABEL_COMPLETION: marker::LabelCompletion;
+}
+
+impl OperandElementType for ElementType<'_> {
+    const LABEL_COMPLETION: marker::LabelCompletion = marker::LabelCompletion::OperandElementType;
+}
+
+impl OperandElementType for VectorElementType<'_> {
+    const LABEL_COMPLETION: marker::LabelCompletion =
+        marker::LabelCompletion::VectorOperandElementType;
+}
+
+impl<E: OperandElementType> ParseTree for RegisterOperand<'_, E> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Operand("register_operand")
+    }
+
+    fn scope_label_completion(&self, label: marker::LabelCompletion) -> marker::LabelCompletion {
+        match label {
+            marker::LabelCompletion::OperandElementType => E::LABEL_COMPLETION,
+            label => label,
+        }
+    }
+
+    fn range(&self) -> Range {
+        self.range
+    }
+
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        let mut children: Vec<&dyn ParseTree> = vec![&self.direction, &self.storage];
+        if let Some(element_type) = &self.element_type {
+            children.push(element_type);
+        }
+        children.push(&self.identifier);
+        children
+    }
+}
+
+/// An immediate field of the instruction encoding, e.g. `imm12 offset`.
+///
+/// - [`immediate_operand`] <- [`token::Immediate`] [`token::Identifier`]
+#[derive(Debug, Getters)]
+pub struct ImmediateOperand<'a> {
+    range: Range,
+    #[getset(get = "pub")]
+    immediate: token::Immediate<'a>,
+    #[getset(get = "pub")]
+    identifier: token::Identifier<'a>,
+}
+
+pub fn immediate_operand<'a>(
+    input: LocatedSpan<&'a str>,
+) -> IResult<LocatedSpan<&'a str>, ImmediateOperand<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+    let (s, immediate) = token::immediate().parse(input)?;
+    let (s, identifier) = token::identifier().parse(s)?;
+    let range = Range {
+        start: input.into(),
+        end: s.into(),
+    };
+    Ok((
+        s,
+        ImmediateOperand {
+            immediate,
+            identifier,
+            range,
+        },
+    ))
+}
+
+impl ImmediateOperand<'_> {
+    /// Returns the width in bits of the immediate, e.g. `12` for `imm12`.
+    pub fn width(&self) -> u32 {
+        keyword_width(self.immediate.token())
+    }
+}
+
+impl ParseTree for ImmediateOperand<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Operand("immediate_operand")
+    }
+
+    fn range(&self) -> Range {
+        self.range
+    }
+
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        vec![&self.immediate, &self.identifier]
+    }
+}
+
+/// A function field of the instruction encoding, e.g. `func3 mode`.
+///
+/// - [`function_operand`] <- [`token::Function`] [`token::Identifier`]
+#[derive(Debug, Getters)]
+pub struct FunctionOperand<'a> {
+    range: Range,
+    #[getset(get = "pub")]
+    function: token::Function<'a>,
+    #[getset(get = "pub")]
+    identifier: token::Identifier<'a>,
+}
+
+pub fn function_operand<'a>(
+    input: LocatedSpan<&'a str>,
+) -> IResult<LocatedSpan<&'a str>, FunctionOperand<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+    let (s, function) = token::function().parse(input)?;
+    let (s, identifier) = token::identifier().parse(s)?;
+    let range = Range {
+        start: input.into(),
+        end: s.into(),
+    };
+    Ok((
+        s,
+        FunctionOperand {
+            function,
+            identifier,
+            range,
+        },
+    ))
+}
+
+impl FunctionOperand<'_> {
+    /// Returns the width in bits of the function field, e.g. `3` for `func3`.
+    pub fn width(&self) -> u32 {
+        keyword_width(self.function.token())
+    }
+}
+
+impl ParseTree for FunctionOperand<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Operand("function_operand")
+    }
+
+    fn range(&self) -> Range {
+        self.range
+    }
+
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        vec![&self.function, &self.identifier]
+    }
+}
+
+/// A control and status register operand in the list of the `csr_operand`
+/// attribute of an instruction, e.g. `in vl`.
+///
+/// - [`csr_operand`] <- [`direction`] [`token::Identifier`]
+#[derive(Debug, Getters)]
+pub struct CsrOperand<'a> {
+    range: Range,
+    #[getset(get = "pub")]
+    direction: Direction<'a>,
+    #[getset(get = "pub")]
+    identifier: token::Identifier<'a>,
+}
+
+pub fn csr_operand<'a>(
+    input: LocatedSpan<&'a str>,
+) -> IResult<LocatedSpan<&'a str>, CsrOperand<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+    let (s, direction) = direction(input)?;
+    let (s, identifier) = token::identifier().parse(s)?;
+    let range = Range {
+        start: input.into(),
+        end: s.into(),
+    };
+    Ok((
+        s,
+        CsrOperand {
+            direction,
+            identifier,
+            range,
+        },
+    ))
+}
+
+impl ParseTree for CsrOperand<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Operand("csr_operand")
+    }
+
+    fn range(&self) -> Range {
+        self.range
+    }
+
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        vec![&self.direction, &self.identifier]
+    }
+}
+
+define_choice!(
+    /// The direction of an operand. It is followed by the storage of the operand.
+    ///
+    /// - [`direction`] <- [`token::Input`] / [`token::Output`] / [`token::InOut`]
+    Direction, pub fn direction,
+    In(token::Input<'a>) => |s| token::input()
+        .set_label_completion(marker::LabelCompletion::OperandStorage)
+        .parse(s),
+    Out(token::Output<'a>) => |s| token::output()
+        .set_label_completion(marker::LabelCompletion::OperandStorage)
+        .parse(s),
+    Io(token::InOut<'a>) => |s| token::in_out()
+        .set_label_completion(marker::LabelCompletion::OperandStorage)
+        .parse(s),
+);
+
+define_choice!(
+    /// The storage of an operand: a register file, a memory or the name of an ACR or ACM.
+    /// It is followed by the optional element type of the operand.
+    ///
+    /// - [`storage`] <- [`token::Xrf`] / [`token::Frf`] / [`token::Vrf`] / [`token::Memory`]
+    ///   / [`token::Identifier`]
+    Storage, pub fn storage,
+    Xrf(token::Xrf<'a>) => |s| token::xrf()
+        .set_label_completion(marker::LabelCompletion::OperandElementType)
+        .parse(s),
+    Frf(token::Frf<'a>) => |s| token::frf()
+        .set_label_completion(marker::LabelCompletion::OperandElementType)
+        .parse(s),
+    Vrf(token::Vrf<'a>) => |s| token::vrf()
+        .set_label_completion(marker::LabelCompletion::OperandElementType)
+        .parse(s),
+    Memory(token::Memory<'a>) => |s| token::memory()
+        .set_label_completion(marker::LabelCompletion::OperandElementType)
+        .parse(s),
+    Custom(token::Identifier<'a>) => |s| token::identifier()
+        .set_label_completion(marker::LabelCompletion::OperandElementType)
+        .parse(s),
+);
+
+define_choice!(
+    /// The type of the elements held by an operand.
+    ///
+    /// - [`element_type`] <- [`token::Uint`] / [`token::Int`] / [`token::Fp`] / [`token::Mask`]
+    ElementType, pub fn element_type,
+    Uint(token::Uint<'a>) => token::uint(),
+    Int(token::Int<'a>) => token::int(),
+    Fp(token::Fp<'a>) => token::fp(),
+    Mask(token::Mask<'a>) => token::mask(),
+);
+
+define_choice!(
+    /// The type of the elements held by an operand of a vector instruction.
+    ///
+    /// - [`vector_element_type`] <- [`token::DoubleWidthUint`] / [`token::DoubleWidthInt`]
+    ///   / [`token::DoubleWidthFp`] / [`token::Uint`] / [`token::Int`] / [`token::Fp`]
+    ///   / [`token::Mask`]
+    VectorElementType, pub fn vector_element_type,
+    DoubleWidthUint(token::DoubleWidthUint<'a>) => token::double_width_uint(),
+    DoubleWidthInt(token::DoubleWidthInt<'a>) => token::double_width_int(),
+    DoubleWidthFp(token::DoubleWidthFp<'a>) => token::double_width_fp(),
+    Uint(token::Uint<'a>) => token::uint(),
+    Int(token::Int<'a>) => token::int(),
+    Fp(token::Fp<'a>) => token::fp(),
+    Mask(token::Mask<'a>) => token::mask(),
+);
+
+/// Returns the width encoded in the numeric suffix of a keyword like `imm12` or `mem32`.
+fn keyword_width(keyword: &LocatedSpan<&str>) -> u32 {
+    keyword
+        .trim_start_matches(|c: char| c.is_alphabetic())
+        .parse()
+        .expect("keyword should end with its width")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the completions on the storage of the operand `in vrf a` of `statement`.
+    fn storage_completions(statement: &str) -> Vec<&'static str> {
+        let source = format!("{statement} add {{\n    op = {{in vrf a}};\n}};\n");
+        let ace = ace(&source);
+        assert_eq!(ace.errors(), &[], "{source}");
+        ace.query(1, 14).show_completions()
+    }
+
+    #[test]
+    fn vector_operands_complete_double_width_element_types() {
+        for statement in ["vector", "rvv_insn"] {
+            assert_eq!(
+                storage_completions(statement),
+                marker::LabelCompletion::VectorOperandElementType.completion()
+            );
+        }
+        assert!(storage_completions("vector").contains(&literal::token::DOUBLE_WIDTH_UINT));
+    }
+
+    #[test]
+    fn scalar_operands_complete_scalar_element_types() {
+        let completions = storage_completions("insn");
+        assert_eq!(
+            completions,
+            marker::LabelCompletion::OperandElementType.completion()
+        );
+        assert!(!completions.contains(&literal::token::DOUBLE_WIDTH_UINT));
+    }
+
+    /// Returns the completions at the end of the unfinished line `    op = {operands` of
+    /// an instruction followed by the line `rest`, and the messages of its syntax errors.
+    fn unfinished_completions(operands: &str, rest: &str) -> (Vec<&'static str>, Vec<String>) {
+        let source = format!("insn add {{\n    op = {{{operands}\n{rest}\n}};\n");
+        let ace = ace(&source);
+        let character = "    op = {".len() + operands.len();
+        let errors = ace.errors().iter().map(|e| e.message().clone()).collect();
+        (ace.query(1, character as u32).show_completions(), errors)
+    }
+
+    #[test]
+    fn unfinished_operands_complete_their_next_part() {
+        let (completions, errors) = unfinished_completions("in ", "};");
+        assert_eq!(
+            completions,
+            marker::LabelCompletion::OperandStorage.completion()
+        );
+        assert_eq!(errors, ["expected storage", "expected identifier"]);
+
+        let (completions, errors) = unfinished_completions("in gpr ", "};");
+        assert_eq!(
+            completions,
+            marker::LabelCompletion::OperandElementType.completion()
+        );
+        assert_eq!(errors, ["expected identifier"]);
+
+        let (completions, errors) = unfinished_completions("in gpr rs1, ", "};");
+        assert_eq!(completions, marker::LabelCompletion::Operand.completion());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].ends_with("found `}`"), "{errors:?}");
+    }
+
+    #[test]
+    fn unterminated_operand_list_keeps_the_next_attribute() {
+        let (completions, errors) = unfinished_completions("in gpr rs1, ", "    latency = 1;");
+        assert_eq!(completions, marker::LabelCompletion::Operand.completion());
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].ends_with("found `latency`"), "{errors:?}");
+        assert_eq!(errors[1..], ["expected `}`", "expected `;`"]);
+
+        let source = "insn add {\n    op = {in gpr rs1, \n    latency = 1;\n};\n";
+        let ace = ace(source);
+        let Statement::Instruction(instruction) = &ace.statements()[0] else {
+            panic!("expected an instruction statement");
+        };
+        assert_eq!(
+            instruction.attributes()[1].node_kind(),
+            NodeKind::Attribute("latency")
+        );
+    }
+
+    #[test]
+    fn unfinished_sequence_completes_its_items() {
+        let ace = ace("test_seq s {\n    seq = {add, };\n};\n");
+        assert_eq!(ace.errors().len(), 1);
+        assert_eq!(
+            ace.query(1, 15).show_completions(),
+            marker::LabelCompletion::SequenceItem.completion()
+        );
+    }
+}
@@ -0,0 +1,772 @@
+use super::*;
+use miette::SourceSpan;
+use nom::error::{ErrorKind, ParseError};
+use nom::InputTake;
+use nom_supreme::error::{BaseErrorKind, Expectation, StackContext};
+
+/// A syntax error found while parsing, with the range of the source it concerns.
+#[derive(Debug, Clone, Eq, PartialEq, Getters)]
+pub struct SyntaxError {
+    #[getset(get = "pub")]
+    kind: SyntaxErrorKind,
+    #[getset(get = "pub")]
+    range: Range,
+    /// The byte offset and length of the source the error concerns.
+    #[getset(get = "pub")]
+    span: SourceSpan,
+    /// The byte offset and length of the input found where the parser fails.
+    #[getset(get = "pub")]
+    location: SourceSpan,
+    /// The tokens the parser expects at the location, e.g. `` `;` `` or `identifier`.
+    #[getset(get = "pub")]
+    expected: Vec<String>,
+    /// The input found at the location, or `None` if the error is a missing token.
+    #[getset(get = "pub")]
+    found: Option<String>,
+    #[getset(get = "pub")]
+    message: String,
+}
+
+impl SyntaxError {
+    /// Returns whether this error is only a warning, which does not prevent the file
+    /// from being loaded.
+    pub fn is_warning(&self) -> bool {
+        self.kind == SyntaxErrorKind::UnknownSetting
+    }
+}
+
+/// The kind of a [`SyntaxError`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SyntaxErrorKind {
+    /// The input cannot be derived from the grammar and is skipped.
+    Unexpected,
+    /// An expected token is missing.
+    Missing,
+    /// A value is derived from the grammar but is not allowed, e.g. a number out of the
+    /// range of its attribute.
+    InvalidValue,
+    /// A setting has a key unknown to the grammar. The setting is kept, and the error
+    /// is only a warning.
+    UnknownSetting,
+}
+
+/// A node covering a part of the source which cannot be derived from the grammar.
+///
+/// The recovering parser skips the erroneous input up to the next `;` or `}` at the
+/// nesting level where the error occurs, keeps it in this node, and continues parsing.
+#[derive(Debug, Getters)]
+pub struct Error<'a> {
+    range: Range,
+    label_completion: marker::LabelCompletion,
+    /// The skipped input.
+    #[getset(get = "pub")]
+    skipped: LocatedSpan<&'a str>,
+    /// The spacing and comments found after the skipped input.
+    #[getset(get = "pub")]
+    spacing: Vec<LocatedSpan<&'a str>>,
+    /// The error reported by the parser which fails at the start of the skipped input.
+    #[getset(get = "pub")]
+    cause: ErrorTree<LocatedSpan<&'a str>>,
+    unterminated_comment: Option<UnterminatedComment<'a>>,
+}
+
+impl ParseTree for Error<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Error
+    }
+
+    fn range(&self) -> Range {
+        self.range
+    }
+
+    fn label_completion(&self) -> marker::LabelCompletion {
+        self.label_completion
+    }
+
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        self.unterminated_comment
+            .iter()
+            .map(|comment| comment as &dyn ParseTree)
+            .collect()
+    }
+
+    fn syntax_error(&self) -> Option<SyntaxError> {
+        let mut location = self.skipped;
+        let mut expected = vec![];
+        expectations(&self.cause, &mut location, &mut expected);
+        let rest = *location.fragment();
+        let (found, length) = match rest.chars().next() {
+            None => (String::from("end of file"), 0),
+            Some(c) if c.is_alphanumeric() || c == '_' => {
+                let word = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .map_or(rest, |end| &rest[..end]);
+                (format!("`{word}`"), word.len())
+            }
+            Some(c) => (format!("`{c}`"), c.len_utf8()),
+        };
+        let message = match expected.as_slice() {
+            [] => format!("unexpected {found}"),
+            [token] => format!("expected {token}, found {found}"),
+            [first, second] => format!("expected {first} or {second}, found {found}"),
+            [tokens @ .., last] => {
+                format!(
+                    "expected one of {} or {last}, found {found}",
+                    tokens.join(", ")
+                )
+            }
+        };
+        Some(SyntaxError {
+            kind: SyntaxErrorKind::Unexpected,
+            range: self.range,
+            span: (self.skipped.location_offset(), self.skipped.len()).into(),
+            location: (location.location_offset(), length).into(),
+            expected,
+            found: Some(found),
+            message,
+        })
+    }
+}
+
+/// The block comment ending the spacing of an [`Error`] node which is not closed by `*/`.
+/// Its range is empty and located at the end of the input, where the `*/` is missing.
+#[derive(Debug)]
+struct UnterminatedComment<'a> {
+    comment: LocatedSpan<&'a str>,
+}
+
+impl ParseTree for UnterminatedComment<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Missing
+    }
+
+    fn range(&self) -> Range {
+        let (end, _) = self.comment.take_split(self.comment.len());
+        Range {
+            start: end.into(),
+            end: end.into(),
+        }
+    }
+
+    fn syntax_error(&self) -> Option<SyntaxError> {
+        unterminated_comment(std::slice::from_ref(&self.comment))
+    }
+}
+
+/// Collects into `expected` the descriptions of the tokens expected by `cause` at its
+/// furthest failure, whose input is kept in `furthest`. A token is described by the
+/// innermost context of its error, or by its tag if it has none.
+fn expectations<'a>(
+    cause: &ErrorTree<LocatedSpan<&'a str>>,
+    furthest: &mut LocatedSpan<&'a str>,
+    expected: &mut Vec<String>,
+) {
+    let (base, context) = match cause {
+        ErrorTree::Alt(siblings) => {
+            siblings
+                .iter()
+                .for_each(|sibling| expectations(sibling, furthest, expected));
+            return;
+        }
+        ErrorTree::Stack { base, contexts } => (
+            base.as_ref(),
+            contexts.iter().find_map(|(_, context)| match context {
+                StackContext::Context(context) => Some(*context),
+                StackContext::Kind(_) => None,
+            }),
+        ),
+        base => (base, None),
+    };
+    let (location, kind) = match base {
+        ErrorTree::Base { location, kind } => (location, kind),
+        nested => return expectations(nested, furthest, expected),
+    };
+    let description = match (context, kind) {
+        (Some(context), _) => context.to_string(),
+        (None, BaseErrorKind::Expected(Expectation::Tag(tag))) => format!("`{tag}`"),
+        (None, BaseErrorKind::Expected(expectation)) => expectation.to_string(),
+        (None, _) => return,
+    };
+    if location.location_offset() > furthest.location_offset() {
+        expected.clear();
+    }
+    if location.location_offset() >= furthest.location_offset() {
+        *furthest = *location;
+    }
+    if location.location_offset() == furthest.location_offset() && !expected.contains(&description)
+    {
+        expected.push(description);
+    }
+}
+
+/// Returns the error of the block comment which ends `spacing` if it is not closed by
+/// `*/`, in which case it runs until the end of the input.
+pub fn unterminated_comment(spacing: &[LocatedSpan<&str>]) -> Option<SyntaxError> {
+    let comment = spacing.last()?;
+    let terminated = comment.len()
+        >= literal::token::LEFT_MULTILINE_COMMENT.len()
+            + literal::token::RIGHT_MULTILINE_COMMENT.len()
+        && comment.ends_with(literal::token::RIGHT_MULTILINE_COMMENT);
+    if !comment.starts_with(literal::token::LEFT_MULTILINE_COMMENT) || terminated {
+        return None;
+    }
+    let (end, _) = comment.take_split(comment.len());
+    Some(SyntaxError {
+        kind: SyntaxErrorKind::Missing,
+        range: Range {
+            start: (*comment).into(),
+            end: end.into(),
+        },
+        span: (comment.location_offset(), comment.len()).into(),
+        location: (end.location_offset(), 0).into(),
+        expected: vec![format!("`{}`", literal::token::RIGHT_MULTILINE_COMMENT)],
+        found: None,
+        message: format!(
+            "unterminated block comment, expected `{}`",
+            literal::token::RIGHT_MULTILINE_COMMENT
+        ),
+    })
+}
+
+/// Returns the error of the `string` literal which is not closed by `"` on its line.
+pub fn unterminated_string(string: LocatedSpan<&str>) -> SyntaxError {
+    unterminated(string, "string literal", literal::token::QUOTE)
+}
+
+/// Returns the error of the block of `code` which is not closed by `%}`, and runs until
+/// the end of the input.
+pub fn unterminated_code_block(code: LocatedSpan<&str>) -> SyntaxError {
+    unterminated(code, "code block", literal::token::RIGHT_PERCENT_BRACE)
+}
+
+/// Returns the error of the `token`, a `what`, which is not closed by `closing`.
+fn unterminated(token: LocatedSpan<&str>, what: &str, closing: &str) -> SyntaxError {
+    let (end, _) = token.take_split(token.len());
+    SyntaxError {
+        kind: SyntaxErrorKind::Missing,
+        range: Range {
+            start: token.into(),
+            end: end.into(),
+        },
+        span: (token.location_offset(), token.len()).into(),
+        location: (end.location_offset(), 0).into(),
+        expected: vec![format!("`{closing}`")],
+        found: None,
+        message: format!("unterminated {what}, expected `{closing}`"),
+    }
+}
+
+/// Returns the error of the `value` derived from the grammar which is not allowed, and
+/// is expected to be the `expected` one, e.g. `a power of two`.
+pub fn invalid_value(value: LocatedSpan<&str>, expected: &str, message: String) -> SyntaxError {
+    let (end, _) = value.take_split(value.len());
+    SyntaxError {
+        kind: SyntaxErrorKind::InvalidValue,
+        range: Range {
+            start: value.into(),
+            end: end.into(),
+        },
+        span: (value.location_offset(), value.len()).into(),
+        location: (value.location_offset(), value.len()).into(),
+        expected: vec![expected.to_string()],
+        found: Some(format!("`{}`", value.fragment())),
+        message,
+    }
+}
+
+/// Returns the warning of the setting whose `key` is none of the `known` keys.
+pub fn unknown_setting(key: LocatedSpan<&str>, known: &[&str]) -> SyntaxError {
+    let (end, _) = key.take_split(key.len());
+    SyntaxError {
+        kind: SyntaxErrorKind::UnknownSetting,
+        range: Range {
+            start: key.into(),
+            end: end.into(),
+        },
+        span: (key.location_offset(), key.len()).into(),
+        location: (key.location_offset(), key.len()).into(),
+        expected: known.iter().map(|key| format!("`{key}`")).collect(),
+        found: Some(format!("`{}`", key.fragment())),
+        message: format!("unknown setting `{}`", key.fragment()),
+    }
+}
+
+/// A node standing for a token which is expected but missing in the source.
+/// Its range is empty and located where the token is expected.
+#[derive(Debug, Getters)]
+pub struct Missing {
+    range: Range,
+    offset: usize,
+    /// The expected token, or the name of the expected node, e.g. `identifier`.
+    #[getset(get = "pub")]
+    expected: &'static str,
+    /// Whether the expected token is a literal, which is quoted in the error.
+    literal: bool,
+}
+
+impl ParseTree for Missing {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Missing
+    }
+
+    fn range(&self) -> Range {
+        self.range
+    }
+
+    fn syntax_error(&self) -> Option<SyntaxError> {
+        let expected = if self.literal {
+            format!("`{}`", self.expected)
+        } else {
+            self.expected.to_string()
+        };
+        Some(SyntaxError {
+            kind: SyntaxErrorKind::Missing,
+            range: self.range,
+            span: (self.offset, 0).into(),
+            location: (self.offset, 0).into(),
+            message: format!("expected {expected}"),
+            expected: vec![expected],
+            found: None,
+        })
+    }
+}
+
+/// A token which is either found in the source or [`Missing`].
+#[derive(Debug)]
+pub enum Expected<T> {
+    Found(T),
+    Missing(Missing),
+}
+
+impl<T> Expected<T> {
+    /// Returns the token if it is found in the source.
+    pub fn found(&self) -> Option<&T> {
+        match self {
+            Expected::Found(found) => Some(found),
+            Expected::Missing(_) => None,
+        }
+    }
+}
+
+impl<T: ParseTree> ParseTree for Expected<T> {
+    fn node_kind(&self) -> NodeKind {
+        match self {
+            Expected::Found(found) => found.node_kind(),
+            Expected::Missing(missing) => missing.node_kind(),
+        }
+    }
+
+    fn range(&self) -> Range {
+        match self {
+            Expected::Found(found) => found.range(),
+            Expected::Missing(missing) => missing.range(),
+        }
+    }
+
+    fn label_completion(&self) -> marker::LabelCompletion {
+        match self {
+            Expected::Found(found) => found.label_completion(),
+            Expected::Missing(missing) => missing.label_completion(),
+        }
+    }
+
+    fn scope_label_completion(&self, label: marker::LabelCompletion) -> marker::LabelCompletion {
+        match self {
+            Expected::Found(found) => found.scope_label_completion(label),
+            Expected::Missing(missing) => missing.scope_label_completion(label),
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        match self {
+            Expected::Found(found) => found.children(),
+            Expected::Missing(missing) => missing.children(),
+        }
+    }
+
+    fn syntax_error(&self) -> Option<SyntaxError> {
+        match self {
+            Expected::Found(found) => found.syntax_error(),
+            Expected::Missing(missing) => missing.syntax_error(),
+        }
+    }
+}
+
+/// Returns a parser of the token parsed by `parser`, which produces a [`Missing`]
+/// node without consuming any input if the token is not found.
+pub fn expected<'a, T, P>(
+    parser: P,
+    expected: &'static str,
+) -> impl Parser<LocatedSpan<&'a str>, Expected<T>, ErrorTree<LocatedSpan<&'a str>>>
+where
+    P: Parser<LocatedSpan<&'a str>, T, ErrorTree<LocatedSpan<&'a str>>>,
+{
+    expected_or_missing(parser, expected, true)
+}
+
+/// Returns a parser like [`expected`] of a node which is not a literal token, but
+/// named by `name` in the error of the [`Missing`] node, e.g. `identifier`.
+pub fn expected_named<'a, T, P>(
+    parser: P,
+    name: &'static str,
+) -> impl Parser<LocatedSpan<&'a str>, Expected<T>, ErrorTree<LocatedSpan<&'a str>>>
+where
+    P: Parser<LocatedSpan<&'a str>, T, ErrorTree<LocatedSpan<&'a str>>>,
+{
+    expected_or_missing(parser, name, false)
+}
+
+fn expected_or_missing<'a, T, P>(
+    mut parser: P,
+    expected: &'static str,
+    literal: bool,
+) -> impl Parser<LocatedSpan<&'a str>, Expected<T>, ErrorTree<LocatedSpan<&'a str>>>
+where
+    P: Parser<LocatedSpan<&'a str>, T, ErrorTree<LocatedSpan<&'a str>>>,
+{
+    move |input: LocatedSpan<&'a str>| match parser.parse(input) {
+        Ok((s, found)) => Ok((s, Expected::Found(found))),
+        Err(nom::Err::Error(_)) => Ok((
+            input,
+            Expected::Missing(Missing {
+                range: Range {
+                    start: input.into(),
+                    end: input.into(),
+                },
+                offset: input.location_offset(),
+                expected,
+                literal,
+            }),
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+/// An item of a list, which is either parsed or skipped in an [`Error`] node.
+#[derive(Debug)]
+pub enum Item<'a, T> {
+    Parsed(T),
+    Error(Error<'a>),
+}
+
+impl<T> Item<'_, T> {
+    /// Returns the item if it is parsed.
+    pub fn parsed(&self) -> Option<&T> {
+        match self {
+            Item::Parsed(parsed) => Some(parsed),
+            Item::Error(_) => None,
+        }
+    }
+}
+
+impl<T: ParseTree> ParseTree for Item<'_, T> {
+    fn node_kind(&self) -> NodeKind {
+        match self {
+            Item::Parsed(parsed) => parsed.node_kind(),
+            Item::Error(error) => error.node_kind(),
+        }
+    }
+
+    fn range(&self) -> Range {
+        match self {
+            Item::Parsed(parsed) => parsed.range(),
+            Item::Error(error) => error.range(),
+        }
+    }
+
+    fn label_completion(&self) -> marker::LabelCompletion {
+        match self {
+            Item::Parsed(parsed) => parsed.label_completion(),
+            Item::Error(error) => error.label_completion(),
+        }
+    }
+
+    fn scope_label_completion(&self, label: marker::LabelCompletion) -> marker::LabelCompletion {
+        match self {
+            Item::Parsed(parsed) => parsed.scope_label_completion(label),
+            Item::Error(error) => error.scope_label_completion(label),
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        match self {
+            Item::Parsed(parsed) => parsed.children(),
+            Item::Error(error) => error.children(),
+        }
+    }
+
+    fn syntax_error(&self) -> Option<SyntaxError> {
+        match self {
+            Item::Parsed(parsed) => parsed.syntax_error(),
+            Item::Error(error) => error.syntax_error(),
+        }
+    }
+}
+
+/// Returns a parser of an item of a list, which replaces the item that `item` fails to
+/// parse with an [`Error`] node completed by the `label_completion` of the list. It
+/// stops before the `,` or `}` ending the item, so it cannot fail.
+pub fn item<'a, T, P>(
+    mut item: P,
+    label_completion: marker::LabelCompletion,
+) -> impl Parser<LocatedSpan<&'a str>, Item<'a, T>, ErrorTree<LocatedSpan<&'a str>>>
+where
+    P: Parser<LocatedSpan<&'a str>, T, ErrorTree<LocatedSpan<&'a str>>>,
+{
+    move |s: LocatedSpan<&'a str>| match item.parse(s) {
+        Ok((rest, parsed)) => Ok((rest, Item::Parsed(parsed))),
+        Err(nom::Err::Error(cause) | nom::Err::Failure(cause)) => {
+            let (rest, skipped) = skip(s, cause, Level::List, starts_attribute, label_completion);
+            Ok((rest, Item::Error(skipped)))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns a parser of the attributes of a block, which replaces every attribute that
+/// `attribute` fails to parse with an [`Error`] node wrapped by `error`. It stops
+/// before the `}` closing the block or at the end of the input.
+pub fn attributes<'a, T, P>(
+    mut attribute: P,
+    error: fn(Error<'a>) -> T,
+) -> impl Parser<LocatedSpan<&'a str>, Vec<T>, ErrorTree<LocatedSpan<&'a str>>>
+where
+    P: Parser<LocatedSpan<&'a str>, T, ErrorTree<LocatedSpan<&'a str>>>,
+{
+    move |mut s: LocatedSpan<&'a str>| {
+        let mut attributes = vec![];
+        while !s.is_empty() && !s.starts_with(literal::token::RIGHT_BRACE) {
+            match attribute.parse(s) {
+                Ok((rest, parsed)) => {
+                    s = rest;
+                    attributes.push(parsed);
+                }
+                Err(nom::Err::Error(cause) | nom::Err::Failure(cause)) => {
+                    let (rest, skipped) = skip(
+                        s,
+                        cause,
+                        Level::Block,
+                        starts_attribute,
+                        marker::LabelCompletion::Attribute,
+                    );
+                    s = rest;
+                    attributes.push(error(skipped));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok((s, attributes))
+    }
+}
+
+/// Returns a parser of the statements of a file, which replaces every statement that
+/// `statement` fails to parse with an [`Error`] node wrapped by `error`. It consumes the
+/// whole input, so it cannot fail.
+pub fn statements<'a, T, P>(
+    mut statement: P,
+    error: fn(Error<'a>) -> T,
+) -> impl FnMut(LocatedSpan<&'a str>) -> Vec<T>
+where
+    P: Parser<LocatedSpan<&'a str>, T, ErrorTree<LocatedSpan<&'a str>>>,
+{
+    move |mut s: LocatedSpan<&'a str>| {
+        let mut statements = vec![];
+        while !s.is_empty() {
+            match statement.parse(s) {
+                Ok((rest, parsed)) => {
+                    s = rest;
+                    statements.push(parsed);
+                }
+                Err(e) => {
+                    let cause = match e {
+                        nom::Err::Error(cause) | nom::Err::Failure(cause) => cause,
+                        nom::Err::Incomplete(_) => {
+                            ErrorTree::from_error_kind(s, ErrorKind::Complete)
+                        }
+                    };
+                    let (rest, skipped) = skip(
+                        s,
+                        cause,
+                        Level::File,
+                        starts_statement,
+                        marker::LabelCompletion::Statement,
+                    );
+                    s = rest;
+                    statements.push(error(skipped));
+                }
+            }
+        }
+        statements
+    }
+}
+
+/// Returns whether `line` starts like an attribute, with a key followed by `=`.
+fn starts_attribute(line: &str) -> bool {
+    let key = line.find(|c| !token::is_word_char(c)).unwrap_or(line.len());
+    key > 0 && line[key..].trim_start().starts_with(literal::token::EQUAL)
+}
+
+/// Returns whether `line` starts like a statement, with the keyword of a statement or
+/// an `#include` directive.
+fn starts_statement(line: &str) -> bool {
+    let keyword = line.find(|c| !token::is_word_char(c)).unwrap_or(line.len());
+    token::is_statement_keyword(&line[..keyword])
+        || line.starts_with(literal::token::INCLUDE_DIRECTIVE)
+}
+
+/// Where the input skipped by [`skip`] is nested.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Level {
+    /// A statement of the file.
+    File,
+    /// An attribute in the braces of a statement.
+    Block,
+    /// An item in the braces of a list.
+    List,
+}
+
+/// Skips the erroneous input up to and including the next `;` at the nesting level of
+/// its start. Inside a block, it stops before the `}` closing the block, and inside a
+/// list, before the `,`, `;` or `}` ending the item. It also stops before a line which
+/// starts at that nesting level with input accepted by `resync`, which only looks at the
+/// first words of the line, so that skipping is linear in the length of the skipped
+/// input. An item of a list is not skipped at all if it starts like that, since no item
+/// holds a `=`. Braces, code blocks, string literals and comments within the skipped
+/// input are balanced.
+fn skip<'a>(
+    input: LocatedSpan<&'a str>,
+    cause: ErrorTree<LocatedSpan<&'a str>>,
+    level: Level,
+    resync: fn(&str) -> bool,
+    label_completion: marker::LabelCompletion,
+) -> (LocatedSpan<&'a str>, Error<'a>) {
+    let text = input.fragment().as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+    let end = loop {
+        if i >= text.len() {
+            break text.len();
+        }
+        let line_start = if i == 0 {
+            level == Level::List
+        } else {
+            text[i - 1] == b'\n'
+        };
+        if depth == 0 && line_start {
+            let indent = text[i..]
+                .iter()
+                .take_while(|c| c.is_ascii_whitespace())
+                .count();
+            if resync(&input.fragment()[i + indent..]) {
+                break i;
+            }
+        }
+        match (text[i], text.get(i + 1)) {
+            (b';', _) if depth == 0 => break if level == Level::List { i } else { i + 1 },
+            (b',', _) if depth == 0 && level == Level::List => break i,
+            (b'{', _) => depth += 1,
+            (b'}', _) if depth == 0 => break if level == Level::File { i + 1 } else { i },
+            (b'}', _) => depth -= 1,
+            (b'%', Some(b'{')) => {
+                let (rest, _) = input.take_split(i);
+                match token::embedded_code(rest) {
+                    Ok((rest, _)) => i = input.len() - rest.len(),
+                    Err(_) => i = text.len(),
+                }
+                continue;
+            }
+            (b'"', _) => {
+                i += 1;
+                while i < text.len() && text[i] != b'"' && text[i] != b'\n' {
+                    if text[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            (b'/', Some(b'/')) => {
+                while i < text.len() && text[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            (b'/', Some(b'*')) => {
+                i += 2;
+                while i < text.len() && !text[i..].starts_with(b"*/") {
+                    i += 1;
+                }
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    };
+    let (s, skipped) = input.take_split(end.min(text.len()));
+    let (s, spacing) = token::split_spacing(s);
+    (
+        s,
+        Error {
+            range: Range {
+                start: input.into(),
+                end: s.into(),
+            },
+            label_completion,
+            skipped,
+            unterminated_comment: unterminated_comment(&spacing).and(
+                spacing
+                    .last()
+                    .map(|&comment| UnterminatedComment { comment }),
+            ),
+            spacing,
+            cause,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statements_resync_at_statement_keyword() {
+        let ace = ace("garbage here\nmore garbage\ncpu {\n    vlen = 512;\n};\n");
+        assert_eq!(ace.statements().len(), 2);
+        assert_eq!(ace.statements()[0].node_kind(), NodeKind::Error);
+        assert_eq!(ace.statements()[1].node_kind(), NodeKind::Statement("cpu"));
+    }
+
+    #[test]
+    fn attributes_resync_at_attribute_key() {
+        let ace = ace("cpu {\n    vlen 512\n    elen = 64;\n};\n");
+        let Statement::Cpu(cpu) = &ace.statements()[0] else {
+            panic!("expected a cpu statement");
+        };
+        assert_eq!(cpu.attributes().len(), 2);
+        assert_eq!(cpu.attributes()[0].node_kind(), NodeKind::Error);
+        assert_eq!(cpu.attributes()[1].node_kind(), NodeKind::Attribute("elen"));
+    }
+
+    #[test]
+    fn long_garbage_is_skipped_in_one_error() {
+        let source = "x y z\n".repeat(50_000) + "cpu {\n    vlen = 512;\n};\n";
+        let ace = ace(&source);
+        assert_eq!(ace.statements().len(), 2);
+        assert_eq!(ace.errors().len(), 1);
+    }
+
+    #[test]
+    fn unterminated_comment_after_skipped_input_is_reported() {
+        let garbage = ace("garbage;\n/* unterminated");
+        let messages: Vec<_> = garbage.errors().iter().map(SyntaxError::message).collect();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].ends_with("found `garbage`"));
+        assert_eq!(messages[1], "unterminated block comment, expected `*/`");
+
+        let cpu = ace("cpu {\n  vlen 5;\n  /* unterminated");
+        let error = cpu
+            .errors()
+            .iter()
+            .find(|error| error.message().starts_with("unterminated block comment"))
+            .expect("the unterminated comment is reported");
+        assert_eq!(error.span().offset(), "cpu {\n  vlen 5;\n  ".len());
+    }
+}
@@ -1,200 +1,764 @@
 use super::*;
+use paste::paste;
+
+/// Returns the keywords of the `attributes` set in a statement which may be set only
+/// once, so they are no longer completed in it.
+fn set_keywords(attributes: &[impl ParseTree]) -> Vec<&'static str> {
+    attributes
+        .iter()
+        .filter(|attribute| attribute.is_single())
+        .flat_map(|attribute| attribute.keywords())
+        .copied()
+        .collect()
+}
+
+/// This macro generates the parse tree node of a block statement
+/// `keyword identifier? { attribute* };`. It creates the node struct, a parser function
+/// named after the snake case of the struct and the [`ParseTree`] implementation.
+///
+/// The keyword is parsed by the given parser and stored in the field with the given
+/// name. It is followed by an [`token::Identifier`] when `identifier` comes next. The
+/// attributes are parsed by the parser of the given [`define_choice!`] enum, named
+/// after its snake case, and the [`marker::LabelCompletion::Attribute`] completion in
+/// the braces becomes the given variant, without the single attributes already set.
+macro_rules! define_statement {
+    (
+        $struct_name:ident, $keyword:ident: $keyword_type:ty = $keyword_parser:expr, identifier,
+        $attribute:ident => $label:ident $(,)?
+    ) => {
+        define_statement!(
+            @statement $struct_name, $keyword: $keyword_type = $keyword_parser, [identifier],
+            $attribute => $label
+        );
+    };
+    (
+        $struct_name:ident, $keyword:ident: $keyword_type:ty = $keyword_parser:expr,
+        $attribute:ident => $label:ident $(,)?
+    ) => {
+        define_statement!(
+            @statement $struct_name, $keyword: $keyword_type = $keyword_parser, [],
+            $attribute => $label
+        );
+    };
+    (
+        @statement $struct_name:ident, $keyword:ident: $keyword_type:ty = $keyword_parser:expr,
+        [$($identifier:ident)?], $attribute:ident => $label:ident
+    ) => {
+        paste! {
+            #[doc = concat!(
+                " Represents the [`", stringify!([<$struct_name:snake>]), "`] statement of the **ACE** grammar.\n\n",
+                " ## Grammar\n\n",
+                " - [`", stringify!([<$struct_name:snake>]), "`] <- `", stringify!($keyword_parser), "`",
+                $(" [`token::", stringify!([<$identifier:camel>]), "`]",)?
+                " [`token::LeftBrace`] [`", stringify!([<$attribute:snake>]), "`]*",
+                " [`token::RightBrace`] [`token::Semicolon`]"
+            )]
+            #[derive(Debug, Getters)]
+            pub struct $struct_name<'a> {
+                range: Range,
+                #[getset(get = "pub")]
+                $keyword: $keyword_type,
+                $(
+                    #[getset(get = "pub")]
+                    $identifier: token::Identifier<'a>,
+                )?
+                #[getset(get = "pub")]
+                left_brace: token::LeftBrace<'a>,
+                #[getset(get = "pub")]
+                attributes: Vec<$attribute<'a>>,
+                #[getset(get = "pub")]
+                right_brace: recovery::Expected<token::RightBrace<'a>>,
+                #[getset(get = "pub")]
+                semicolon: recovery::Expected<token::Semicolon<'a>>,
+            }
+
+            #[doc = concat!(" Parses the [`", stringify!($struct_name), "`] statement.")]
+            pub fn [<$struct_name:snake>]<'a>(
+                input: LocatedSpan<&'a str>,
+            ) -> IResult<LocatedSpan<&'a str>, $struct_name<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+                let (s, $keyword) = ($keyword_parser).parse(input)?;
+                $(let (s, $identifier) = token::identifier().parse(s)?;)?
+                let (s, left_brace) = token::left_brace()
+                    .set_label_completion(marker::LabelCompletion::Attribute)
+                    .parse(s)?;
+                let (s, attributes) =
+                    recovery::attributes([<$attribute:snake>], $attribute::Error).parse(s)?;
+                let (s, right_brace) =
+                    recovery::expected(token::right_brace(), literal::token::RIGHT_BRACE).parse(s)?;
+                let (s, semicolon) = recovery::expected(
+                    |s| {
+                        token::semicolon()
+                            .set_label_completion(marker::LabelCompletion::Statement)
+                            .parse(s)
+                    },
+                    literal::token::SEMICOLON,
+                )
+                .parse(s)?;
+                let range = Range {
+                    start: input.into(),
+                    end: s.into(),
+                };
+                Ok((
+                    s,
+                    $struct_name {
+                        $keyword,
+                        $($identifier,)?
+                        left_brace,
+                        attributes,
+                        right_brace,
+                        semicolon,
+                        range,
+                    },
+                ))
+            }
+
+            impl ParseTree for $struct_name<'_> {
+                fn node_kind(&self) -> NodeKind {
+                    NodeKind::Statement(stringify!([<$struct_name:snake>]))
+                }
+
+                fn scope_label_completion(
+                    &self,
+                    label: marker::LabelCompletion,
+                ) -> marker::LabelCompletion {
+                    match label {
+                        marker::LabelCompletion::Attribute => marker::LabelCompletion::$label,
+                        label => label,
+                    }
+                }
+
+                fn excluded_completions(&self, label: marker::LabelCompletion) -> Vec<&'static str> {
+                    match label {
+                        marker::LabelCompletion::$label => set_keywords(&self.attributes),
+                        _ => vec![],
+                    }
+                }
+
+                fn range(&self) -> Range {
+                    self.range
+                }
+
+                fn children(&self) -> Vec<&dyn ParseTree> {
+                    let mut children: Vec<&dyn ParseTree> =
+                        vec![&self.$keyword, $(&self.$identifier,)? &self.left_brace];
+                    children.extend(self.attributes.iter().map(|attr| attr as &dyn ParseTree));
+                    children.push(&self.right_brace);
+                    children.push(&self.semicolon);
+                    children
+                }
+            }
+        }
+    };
+}
+
+define_statement!(
+    Cpu,
+    pragma: token::Cpu<'a> = token::cpu(),
+    CpuAttribute => CpuAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`Cpu`] statement.
+    ///
+    /// - [`cpu_attribute`] <- [`attribute::name`] / [`attribute::vlen`] / [`attribute::dlen`]
+    ///   / [`attribute::elen`] / [`attribute::flen`] / [`attribute::felen`]
+    ///   / [`attribute::streaming_port_width`] / [`attribute::address_bits`]
+    ///   / [`attribute::endian`] / [`attribute::march`]
+    CpuAttribute, pub fn cpu_attribute else Error,
+    Name(attribute::Name<'a>) => attribute::name,
+    Vlen(attribute::Vlen<'a>) => attribute::vlen,
+    Dlen(attribute::Dlen<'a>) => attribute::dlen,
+    Elen(attribute::Elen<'a>) => attribute::elen,
+    Flen(attribute::Flen<'a>) => attribute::flen,
+    Felen(attribute::Felen<'a>) => attribute::felen,
+    StreamingPortWidth(attribute::StreamingPortWidth<'a>) => attribute::streaming_port_width,
+    AddressBits(attribute::AddressBits<'a>) => attribute::address_bits,
+    Endian(attribute::Endian<'a>) => attribute::endian,
+    March(attribute::March<'a>) => attribute::march,
+);
+
+define_statement!(
+    Config,
+    pragma: token::Config<'a> = token::config(),
+    ConfigAttribute => ConfigAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`Config`] statement.
+    ///
+    /// - [`config_attribute`] <- [`attribute::timeout_cycle`] / [`attribute::optimization_policy`]
+    ///   / [`attribute::lm_latency`] / [`attribute::bus_latency`] / [`attribute::export_level_ahb`]
+    ///   / [`attribute::export_level_axi`] / [`attribute::export_level_sram`]
+    ///   / [`attribute::export_level_port`] / [`attribute::export_level_streaming_port`]
+    ///   / [`attribute::rf_buffer`] / [`attribute::gpr_buffer`] / [`attribute::frf_buffer`]
+    ///   / [`attribute::custom_error_bits`] / [`attribute::custom_error_en`]
+    ///   / [`attribute::insn_queue`] / [`attribute::group_in_buffer`]
+    ///   / [`attribute::group_out_buffer`] / [`attribute::error_pc`]
+    ///   / [`attribute::clock_domain_crossing_stage`]
+    ConfigAttribute, pub fn config_attribute else Error,
+    TimeoutCycle(attribute::TimeoutCycle<'a>) => attribute::timeout_cycle,
+    OptimizationPolicy(attribute::OptimizationPolicy<'a>) => attribute::optimization_policy,
+    LmLatency(attribute::LmLatency<'a>) => attribute::lm_latency,
+    BusLatency(attribute::BusLatency<'a>) => attribute::bus_latency,
+    ExportLevelAhb(attribute::ExportLevelAhb<'a>) => attribute::export_level_ahb,
+    ExportLevelAxi(attribute::ExportLevelAxi<'a>) => attribute::export_level_axi,
+    ExportLevelSram(attribute::ExportLevelSram<'a>) => attribute::export_level_sram,
+    ExportLevelPort(attribute::ExportLevelPort<'a>) => attribute::export_level_port,
+    ExportLevelStreamingPort(attribute::ExportLevelStreamingPort<'a>) => attribute::export_level_streaming_port,
+    RfBuffer(attribute::RfBuffer<'a>) => attribute::rf_buffer,
+    GprBuffer(attribute::GprBuffer<'a>) => attribute::gpr_buffer,
+    FrfBuffer(attribute::FrfBuffer<'a>) => attribute::frf_buffer,
+    CustomErrorBits(attribute::CustomErrorBits<'a>) => attribute::custom_error_bits,
+    CustomErrorEn(attribute::CustomErrorEn<'a>) => attribute::custom_error_en,
+    InsnQueue(attribute::InsnQueue<'a>) => attribute::insn_queue,
+    GroupInBuffer(attribute::GroupInBuffer<'a>) => attribute::group_in_buffer,
+    GroupOutBuffer(attribute::GroupOutBuffer<'a>) => attribute::group_out_buffer,
+    ErrorPc(attribute::ErrorPc<'a>) => attribute::error_pc,
+    ClockDomainCrossingStage(attribute::ClockDomainCrossingStage<'a>) => attribute::clock_domain_crossing_stage,
+);
+
+define_statement!(
+    Acr,
+    pragma: token::Register<'a> = token::register(),
+    identifier,
+    AcrAttribute => AcrAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`Acr`] statement.
+    ///
+    /// - [`acr_attribute`] <- [`attribute::num`] / [`attribute::width`] / [`attribute::reset_default`] / [`attribute::reset`]
+    AcrAttribute, pub fn acr_attribute else Error,
+    Num(attribute::Num<'a>) => attribute::num,
+    Width(attribute::Width<'a>) => attribute::width,
+    ResetDefault(attribute::ResetDefault<'a>) => attribute::reset_default,
+    Reset(attribute::Reset<'a>) => attribute::reset,
+);
+
+define_statement!(
+    Acm,
+    kind: AcmKind<'a> = acm_kind,
+    identifier,
+    AcmAttribute => AcmAttribute
+);
+
+define_choice!(
+    /// The kind of memory declared by an [`Acm`] statement.
+    ///
+    /// - [`acm_kind`] <- [`token::Ram`] / [`token::Rom`]
+    AcmKind, pub fn acm_kind,
+    Ram(token::Ram<'a>) => token::ram(),
+    Rom(token::Rom<'a>) => token::rom(),
+);
+
+define_choice!(
+    /// An attribute of the [`Acm`] statement.
+    ///
+    /// - [`acm_attribute`] <- [`attribute::width`] / [`attribute::num`] / [`attribute::interface`]
+    ///   / [`attribute::latency`] / [`attribute::byte_enable`] / [`attribute::write_strobe`]
+    ///   / [`attribute::max_burst_length`] / [`attribute::error_detect`]
+    ///   / [`attribute::content_default`] / [`attribute::content`]
+    AcmAttribute, pub fn acm_attribute else Error,
+    Width(attribute::Width<'a>) => attribute::width,
+    Num(attribute::Num<'a>) => attribute::num,
+    Interface(attribute::Interface<'a>) => attribute::interface,
+    Latency(attribute::Latency<'a>) => attribute::latency,
+    ByteEnable(attribute::ByteEnable<'a>) => attribute::byte_enable,
+    WriteStrobe(attribute::WriteStrobe<'a>) => attribute::write_strobe,
+    MaxBurstLength(attribute::MaxBurstLength<'a>) => attribute::max_burst_length,
+    ErrorDetect(attribute::ErrorDetect<'a>) => attribute::error_detect,
+    ContentDefault(attribute::ContentDefault<'a>) => attribute::content_default,
+    Content(attribute::Content<'a>) => attribute::content,
+);
+
+define_statement!(
+    Acp,
+    pragma: token::Port<'a> = token::port(),
+    identifier,
+    AcpAttribute => AcpAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`Acp`] statement.
+    ///
+    /// - [`acp_attribute`] <- [`attribute::io_type`] / [`attribute::width`] / [`attribute::interface`]
+    ///   / [`attribute::export_level`] / [`attribute::access_type`] / [`attribute::byte_enable`]
+    ///   / [`attribute::max_burst_length`]
+    AcpAttribute, pub fn acp_attribute else Error,
+    IoType(attribute::IoType<'a>) => attribute::io_type,
+    Width(attribute::Width<'a>) => attribute::width,
+    Interface(attribute::Interface<'a>) => attribute::interface,
+    ExportLevel(attribute::ExportLevel<'a>) => attribute::export_level,
+    AccessType(attribute::AccessType<'a>) => attribute::access_type,
+    ByteEnable(attribute::ByteEnable<'a>) => attribute::byte_enable,
+    MaxBurstLength(attribute::MaxBurstLength<'a>) => attribute::max_burst_length,
+);
+
+define_statement!(
+    Instruction,
+    pragma: token::Instruction<'a> = token::instruction(),
+    identifier,
+    InstructionAttribute => InstructionAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`Instruction`] statement.
+    ///
+    /// - [`instruction_attribute`] <- [`attribute::operand`] / [`attribute::implied_operand`]
+    ///   / [`attribute::csr_operand`] / [`attribute::insn_encode`] / [`attribute::latency`]
+    ///   / [`attribute::blocking`] / [`attribute::side_effect`] / [`attribute::csim`]
+    ///   / [`attribute::chisel`] / [`attribute::spinalhdl`]
+    InstructionAttribute, pub fn instruction_attribute else Error,
+    Operand(attribute::Operand<'a>) => attribute::operand,
+    ImpliedOperand(attribute::ImpliedOperand<'a>) => attribute::implied_operand,
+    CsrOperand(attribute::CsrOperand<'a>) => attribute::csr_operand,
+    InsnEncode(attribute::InsnEncode<'a>) => attribute::insn_encode,
+    Latency(attribute::Latency<'a>) => attribute::latency,
+    Blocking(attribute::Blocking<'a>) => attribute::blocking,
+    SideEffect(attribute::SideEffect<'a>) => attribute::side_effect,
+    Csim(attribute::Csim<'a>) => attribute::csim,
+    Chisel(attribute::Chisel<'a>) => attribute::chisel,
+    Spinalhdl(attribute::Spinalhdl<'a>) => attribute::spinalhdl,
+);
+
+define_statement!(
+    VectorInstruction,
+    pragma: token::VectorInstruction<'a> = token::vector_instruction(),
+    identifier,
+    VectorInstructionAttribute => VectorInstructionAttribute
+);
+
+define_statement!(
+    RvvInstruction,
+    pragma: token::RvvInstruction<'a> = token::rvv_instruction(),
+    identifier,
+    VectorInstructionAttribute => VectorInstructionAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`VectorInstruction`] and [`RvvInstruction`] statements, which
+    /// are the attributes of the [`Instruction`] statement, with operands whose elements
+    /// may be double-width, and the vector-only attributes.
+    ///
+    /// - [`vector_instruction_attribute`] <- [`attribute::vector_operand`]
+    ///   / [`attribute::vector_implied_operand`] / [`attribute::csr_operand`]
+    ///   / [`attribute::insn_encode`] / [`attribute::latency`] / [`attribute::blocking`]
+    ///   / [`attribute::side_effect`] / [`attribute::csim`] / [`attribute::chisel`]
+    ///   / [`attribute::spinalhdl`] / [`attribute::vector_mask`] / [`attribute::vector_unit`]
+    ///   / [`attribute::rvv_custom_kill`]
+    VectorInstructionAttribute, pub fn vector_instruction_attribute else Error,
+    Operand(attribute::VectorOperand<'a>) => attribute::vector_operand,
+    ImpliedOperand(attribute::VectorImpliedOperand<'a>) => attribute::vector_implied_operand,
+    CsrOperand(attribute::CsrOperand<'a>) => attribute::csr_operand,
+    InsnEncode(attribute::InsnEncode<'a>) => attribute::insn_encode,
+    Latency(attribute::Latency<'a>) => attribute::latency,
+    Blocking(attribute::Blocking<'a>) => attribute::blocking,
+    SideEffect(attribute::SideEffect<'a>) => attribute::side_effect,
+    Csim(attribute::Csim<'a>) => attribute::csim,
+    Chisel(attribute::Chisel<'a>) => attribute::chisel,
+    Spinalhdl(attribute::Spinalhdl<'a>) => attribute::spinalhdl,
+    VectorMask(attribute::VectorMask<'a>) => attribute::vector_mask,
+    VectorUnit(attribute::VectorUnit<'a>) => attribute::vector_unit,
+    RvvCustomKill(attribute::RvvCustomKill<'a>) => attribute::rvv_custom_kill,
+);
+
+define_statement!(
+    BackgroundInstruction,
+    pragma: token::BackgroundInstruction<'a> = token::background_instruction(),
+    identifier,
+    BackgroundInstructionAttribute => BackgroundInstructionAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`BackgroundInstruction`] statement.
+    ///
+    /// - [`background_instruction_attribute`] <- [`attribute::operand`]
+    ///   / [`attribute::implied_operand`] / [`attribute::csr_operand`] / [`attribute::insn_encode`]
+    ///   / [`attribute::latency`] / [`attribute::side_effect`] / [`attribute::csim`]
+    ///   / [`attribute::chisel`] / [`attribute::spinalhdl`] / [`attribute::outstanding_insn_num`]
+    ///   / [`attribute::interrupt`]
+    BackgroundInstructionAttribute, pub fn background_instruction_attribute else Error,
+    Operand(attribute::Operand<'a>) => attribute::operand,
+    ImpliedOperand(attribute::ImpliedOperand<'a>) => attribute::implied_operand,
+    CsrOperand(attribute::CsrOperand<'a>) => attribute::csr_operand,
+    InsnEncode(attribute::InsnEncode<'a>) => attribute::insn_encode,
+    Latency(attribute::Latency<'a>) => attribute::latency,
+    SideEffect(attribute::SideEffect<'a>) => attribute::side_effect,
+    Csim(attribute::Csim<'a>) => attribute::csim,
+    Chisel(attribute::Chisel<'a>) => attribute::chisel,
+    Spinalhdl(attribute::Spinalhdl<'a>) => attribute::spinalhdl,
+    OutstandingInsnNum(attribute::OutstandingInsnNum<'a>) => attribute::outstanding_insn_num,
+    Interrupt(attribute::Interrupt<'a>) => attribute::interrupt,
+);
+
+define_statement!(
+    UtilityInstruction,
+    pragma: token::UtilityInstruction<'a> = token::utility_instruction(),
+    identifier,
+    UtilityInstructionAttribute => UtilityInstructionAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`UtilityInstruction`] statement.
+    ///
+    /// - [`utility_instruction_attribute`] <- [`attribute::operand`]
+    ///   / [`attribute::implied_operand`] / [`attribute::insn_encode`] / [`attribute::latency`]
+    ///   / [`attribute::csim`] / [`attribute::chisel`] / [`attribute::spinalhdl`]
+    ///   / [`attribute::utility_kind`] / [`attribute::utility`]
+    UtilityInstructionAttribute, pub fn utility_instruction_attribute else Error,
+    Operand(attribute::Operand<'a>) => attribute::operand,
+    ImpliedOperand(attribute::ImpliedOperand<'a>) => attribute::implied_operand,
+    InsnEncode(attribute::InsnEncode<'a>) => attribute::insn_encode,
+    Latency(attribute::Latency<'a>) => attribute::latency,
+    Csim(attribute::Csim<'a>) => attribute::csim,
+    Chisel(attribute::Chisel<'a>) => attribute::chisel,
+    Spinalhdl(attribute::Spinalhdl<'a>) => attribute::spinalhdl,
+    UtilityKind(attribute::UtilityKind<'a>) => attribute::utility_kind,
+    Utility(attribute::Utility<'a>) => attribute::utility,
+);
 
 #[derive(Debug, Getters)]
-pub struct Cpu<'a> {
+pub struct CsimHeader<'a> {
     range: Range,
     #[getset(get = "pub")]
-    pragma: token::Cpu<'a>,
-    #[getset(get = "pub")]
-    left_brace: token::LeftBrace<'a>,
+    pragma: token::CsimHeader<'a>,
     #[getset(get = "pub")]
-    attributes: Vec<CpuAttribute<'a>>,
+    code: token::CodeBlock<'a>,
     #[getset(get = "pub")]
-    right_brace: token::RightBrace<'a>,
-    #[getset(get = "pub")]
-    semicolon: token::Semicolon<'a>,
+    semicolon: Option<token::Semicolon<'a>>,
 }
 
-pub fn cpu<'a>(
+pub fn csim_header<'a>(
     input: LocatedSpan<&'a str>,
-) -> IResult<LocatedSpan<&'a str>, Cpu<'a>, ErrorTree<LocatedSpan<&'a str>>> {
-    let (s, pragma) = token::cpu().parse(input)?;
-    let (s, left_brace) = token::left_brace().parse(s)?;
-    let (s, attributes) = many0(cpu_attribute)(s)?;
-    let (s, right_brace) = token::right_brace().parse(s)?;
-    let (s, semicolon) = token::semicolon()
-        .set_label_completion(marker::LabelCompletion::Statement)
-        .parse(s)?;
+) -> IResult<LocatedSpan<&'a str>, CsimHeader<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+    let (s, pragma) = token::csim_header().parse(input)?;
+    let (s, code) = token::code_block().parse(s)?;
+    let (s, semicolon) = opt(|s| {
+        token::semicolon()
+            .set_label_completion(marker::LabelCompletion::Statement)
+            .parse(s)
+    })(s)?;
     let range = Range {
         start: input.into(),
         end: s.into(),
     };
     Ok((
         s,
-        Cpu {
+        CsimHeader {
             pragma,
-            left_brace,
-            attributes,
-            right_brace,
+            code,
             semicolon,
             range,
         },
     ))
 }
 
-impl ParseTree for Cpu<'_> {
+impl ParseTree for CsimHeader<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Statement("csim_header")
+    }
+
     fn range(&self) -> Range {
         self.range
     }
 
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-        if self.pragma.range().contains(pos) {
-            self.pragma.query(pos)
-        } else if self.left_brace.range().contains(pos) {
-            self.left_brace.query(pos)
-        } else if self.right_brace.range().contains(pos) {
-            self.right_brace.query(pos)
-        } else if self.semicolon.range().contains(pos) {
-            self.semicolon.query(pos)
-        } else {
-            self.attributes
-                .iter()
-                .find_map(|attr| {
-                    if attr.range().contains(pos) {
-                        attr.query(pos).ok()
-                    } else {
-                        None
-                    }
-                })
-                .ok_or(())
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        let mut children: Vec<&dyn ParseTree> = vec![&self.pragma, &self.code];
+        if let Some(semicolon) = &self.semicolon {
+            children.push(semicolon);
         }
+        children
     }
 }
 
-#[derive(Debug)]
-pub enum CpuAttribute<'a> {
-    Name(attribute::Name<'a>),
-    Vlen(attribute::Vlen<'a>),
+#[derive(Debug, Getters)]
+pub struct Include<'a> {
+    range: Range,
+    #[getset(get = "pub")]
+    pragma: token::IncludeDirective<'a>,
+    #[getset(get = "pub")]
+    path: token::StringLiteral<'a>,
 }
 
-fn cpu_attribute<'a>(
+pub fn include<'a>(
     input: LocatedSpan<&'a str>,
-) -> IResult<LocatedSpan<&'a str>, CpuAttribute<'a>, ErrorTree<LocatedSpan<&'a str>>> {
-    alt((
-        attribute::name.map(CpuAttribute::Name),
-        attribute::vlen.map(CpuAttribute::Vlen),
-    ))(input)
+) -> IResult<LocatedSpan<&'a str>, Include<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+    let (s, pragma) = token::include_directive().parse(input)?;
+    let (s, path) = token::string_literal()
+        .set_label_completion(marker::LabelCompletion::Statement)
+        .parse(s)?;
+    let range = Range {
+        start: input.into(),
+        end: s.into(),
+    };
+    Ok((
+        s,
+        Include {
+            pragma,
+            path,
+            range,
+        },
+    ))
 }
 
-impl ParseTree for CpuAttribute<'_> {
+impl ParseTree for Include<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Statement("include")
+    }
+
     fn range(&self) -> Range {
-        match self {
-            CpuAttribute::Name(name) => name.range(),
-            CpuAttribute::Vlen(vlen) => vlen.range(),
-        }
+        self.range
     }
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-        match self {
-            CpuAttribute::Name(name) => name.query(pos),
-            CpuAttribute::Vlen(vlen) => vlen.query(pos),
-        }
+
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        vec![&self.pragma, &self.path]
     }
 }
 
 #[derive(Debug, Getters)]
-pub struct Config<'a> {
+pub struct TestPattern<'a> {
     range: Range,
     #[getset(get = "pub")]
-    pragma: token::Config<'a>,
+    pragma: token::TestPattern<'a>,
     #[getset(get = "pub")]
-    left_brace: token::LeftBrace<'a>,
+    identifier: token::Identifier<'a>,
     #[getset(get = "pub")]
-    attributes: Vec<ConfigAttribute<'a>>,
+    code: token::CodeBlock<'a>,
     #[getset(get = "pub")]
-    right_brace: token::RightBrace<'a>,
-    #[getset(get = "pub")]
-    semicolon: token::Semicolon<'a>,
+    semicolon: Option<token::Semicolon<'a>>,
 }
 
-pub fn config<'a>(
+pub fn test_pattern<'a>(
     input: LocatedSpan<&'a str>,
-) -> IResult<LocatedSpan<&'a str>, Config<'a>, ErrorTree<LocatedSpan<&'a str>>> {
-    let (s, pragma) = token::config().parse(input)?;
-    let (s, left_brace) = token::left_brace().parse(s)?;
-    let (s, attributes) = many0(config_attribute)(s)?;
-    let (s, right_brace) = token::right_brace().parse(s)?;
-    let (s, semicolon) = token::semicolon()
-        .set_label_completion(marker::LabelCompletion::Statement)
-        .parse(s)?;
+) -> IResult<LocatedSpan<&'a str>, TestPattern<'a>, ErrorTree<LocatedSpan<&'a str>>> {
+    let (s, pragma) = token::test_pattern().parse(input)?;
+    let (s, identifier) = token::identifier().parse(s)?;
+    let (s, code) = token::code_block().parse(s)?;
+    let (s, semicolon) = opt(|s| {
+        token::semicolon()
+            .set_label_completion(marker::LabelCompletion::Statement)
+            .parse(s)
+    })(s)?;
     let range = Range {
         start: input.into(),
         end: s.into(),
     };
     Ok((
         s,
-        Config {
+        TestPattern {
             pragma,
-            left_brace,
-            attributes,
-            right_brace,
+            identifier,
+            code,
             semicolon,
             range,
         },
     ))
 }
 
-impl ParseTree for Config<'_> {
+impl ParseTree for TestPattern<'_> {
+    fn node_kind(&self) -> NodeKind {
+        NodeKind::Statement("test_pattern")
+    }
+
     fn range(&self) -> Range {
         self.range
     }
 
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-        if self.pragma.range().contains(pos) {
-            self.pragma.query(pos)
-        } else if self.left_brace.range().contains(pos) {
-            self.left_brace.query(pos)
-        } else if self.right_brace.range().contains(pos) {
-            self.right_brace.query(pos)
-        } else if self.semicolon.range().contains(pos) {
-            self.semicolon.query(pos)
-        } else {
-            self.attributes
-                .iter()
-                .find_map(|attr| {
-                    if attr.range().contains(pos) {
-                        attr.query(pos).ok()
-                    } else {
-                        None
-                    }
-                })
-                .ok_or(())
+    fn children(&self) -> Vec<&dyn ParseTree> {
+        let mut children: Vec<&dyn ParseTree> = vec![&self.pragma, &self.identifier, &self.code];
+        if let Some(semicolon) = &self.semicolon {
+            children.push(semicolon);
         }
+        children
     }
 }
 
-#[derive(Debug)]
-pub enum ConfigAttribute<'a> {
-    TimeoutCycle(attribute::TimeoutCycle<'a>),
-}
+define_statement!(
+    TestSequence,
+    pragma: token::TestSequence<'a> = token::test_sequence(),
+    identifier,
+    TestSequenceAttribute => TestSequenceAttribute
+);
 
-fn config_attribute<'a>(
-    input: LocatedSpan<&'a str>,
-) -> IResult<LocatedSpan<&'a str>, ConfigAttribute<'a>, ErrorTree<LocatedSpan<&'a str>>> {
-    attribute::timeout_cycle
-        .map(ConfigAttribute::TimeoutCycle)
-        .parse(input)
-}
+define_choice!(
+    /// An attribute of the [`TestSequence`] statement.
+    ///
+    /// - [`test_sequence_attribute`] <- [`attribute::sequence`] / [`attribute::loop_type`]
+    ///   / [`attribute::stride`] / [`attribute::test_sequence_init`]
+    TestSequenceAttribute, pub fn test_sequence_attribute else Error,
+    Sequence(attribute::Sequence<'a>) => attribute::sequence,
+    LoopType(attribute::LoopType<'a>) => attribute::loop_type,
+    Stride(attribute::Stride<'a>) => attribute::stride,
+    TestSequenceInit(attribute::TestSequenceInit<'a>) => attribute::test_sequence_init,
+);
 
-impl ParseTree for ConfigAttribute<'_> {
-    fn range(&self) -> Range {
-        match self {
-            ConfigAttribute::TimeoutCycle(timeout_cycle) => timeout_cycle.range(),
-        }
+define_statement!(
+    TestbenchSequence,
+    pragma: token::TestbenchSequence<'a> = token::testbench_sequence(),
+    identifier,
+    TestbenchSequenceAttribute => TestbenchSequenceAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`TestbenchSequence`] statement.
+    ///
+    /// - [`testbench_sequence_attribute`] <- [`attribute::sequence_list`]
+    ///   / [`attribute::loop_type`] / [`attribute::stride`]
+    TestbenchSequenceAttribute, pub fn testbench_sequence_attribute else Error,
+    SequenceList(attribute::SequenceList<'a>) => attribute::sequence_list,
+    LoopType(attribute::LoopType<'a>) => attribute::loop_type,
+    Stride(attribute::Stride<'a>) => attribute::stride,
+);
+
+define_statement!(
+    InsnGroup,
+    pragma: token::InsnGroup<'a> = token::insn_group(),
+    identifier,
+    InsnGroupAttribute => InsnGroupAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`InsnGroup`] statement.
+    ///
+    /// - [`insn_group_attribute`] <- [`attribute::instruction_list`]
+    InsnGroupAttribute, pub fn insn_group_attribute else Error,
+    InstructionList(attribute::InstructionList<'a>) => attribute::instruction_list,
+);
+
+define_statement!(
+    Sync,
+    pragma: token::Sync<'a> = token::sync(),
+    identifier,
+    SyncAttribute => SyncAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`Sync`] statement.
+    ///
+    /// - [`sync_attribute`] <- [`attribute::nbsync`] / [`attribute::bsync`]
+    SyncAttribute, pub fn sync_attribute else Error,
+    Nbsync(attribute::Nbsync<'a>) => attribute::nbsync,
+    Bsync(attribute::Bsync<'a>) => attribute::bsync,
+);
+
+define_statement!(
+    Status,
+    pragma: token::Status<'a> = token::status(),
+    identifier,
+    StatusAttribute => StatusAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`Status`] statement.
+    ///
+    /// - [`status_attribute`] <- [`attribute::st_status`] / [`attribute::error_rd`]
+    StatusAttribute, pub fn status_attribute else Error,
+    StStatus(attribute::StStatus<'a>) => attribute::st_status,
+    ErrorRd(attribute::ErrorRd<'a>) => attribute::error_rd,
+);
+
+define_statement!(
+    Resource,
+    pragma: token::Resource<'a> = token::resource(),
+    identifier,
+    ResourceAttribute => ResourceAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`Resource`] statement.
+    ///
+    /// - [`resource_attribute`] <- [`attribute::shared`] / [`attribute::hart_id`]
+    ResourceAttribute, pub fn resource_attribute else Error,
+    Shared(attribute::Shared<'a>) => attribute::shared,
+    HartId(attribute::HartId<'a>) => attribute::hart_id,
+);
+
+define_statement!(
+    AclMiscellaneousSetting,
+    pragma: token::AclMiscellaneousSetting<'a> = token::acl_miscellaneous_setting(),
+    AclMiscellaneousSettingAttribute => AclMiscellaneousSettingAttribute
+);
+
+define_choice!(
+    /// An attribute of the [`AclMiscellaneousSetting`] statement. A setting with any
+    /// other key is accepted by [`attribute::setting`], which is tried last.
+    ///
+    /// - [`acl_miscellaneous_setting_attribute`] <- [`attribute::misc_setting`]
+    ///   / [`attribute::setting`]
+    AclMiscellaneousSettingAttribute, pub fn acl_miscellaneous_setting_attribute else Error,
+    MiscSetting(attribute::MiscSetting<'a>) => attribute::misc_setting,
+    Setting(attribute::Setting<'a>) => attribute::setting,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the completions at the empty line `line` of `source`.
+    fn completions(source: &str, line: u32) -> Vec<&'static str> {
+        let ace = ace(source);
+        assert_eq!(ace.errors(), &[], "{source}");
+        ace.query(line, 4).show_completions()
     }
 
-    fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-        match self {
-            ConfigAttribute::TimeoutCycle(timeout_cycle) => timeout_cycle.query(pos),
-        }
+    #[test]
+    fn cpu_completes_its_attributes_not_yet_set() {
+        assert_eq!(
+            completions("cpu {\n    \n};\n", 1),
+            marker::LabelCompletion::CpuAttribute.completion()
+        );
+        let completions = completions(
+            "cpu {\n    vlen = 512;\n    endian = little;\n    \n};\n",
+            3,
+        );
+        assert!(completions.contains(&"dlen"));
+        assert!(!completions.contains(&"vlen"));
+        assert!(!completions.contains(&"endian"));
+        assert!(!completions.contains(&"rf_buffer"));
+    }
+
+    #[test]
+    fn config_excludes_every_alias_of_a_set_attribute() {
+        let completions = completions("config {\n    rf_buf = auto;\n    \n};\n", 2);
+        assert!(completions.contains(&"gpr_buffer"));
+        assert!(!completions.contains(&"rf_buffer"));
+        assert!(!completions.contains(&"rf_buf"));
+        assert!(!completions.contains(&"vlen"));
+    }
+
+    #[test]
+    fn instruction_completes_its_attributes_not_yet_set() {
+        let completions = completions(
+            "insn add {\n    op = {in gpr a};\n    latency = 1;\n    \n};\n",
+            3,
+        );
+        assert!(completions.contains(&"insn_encode"));
+        assert!(completions.contains(&"csim"));
+        assert!(!completions.contains(&"operand"));
+        assert!(!completions.contains(&"op"));
+        assert!(!completions.contains(&"latency"));
+        assert!(!completions.contains(&"vlen"));
+    }
+
+    #[test]
+    fn repeatable_attributes_are_still_completed() {
+        let completions = completions(
+            "test_seq t {\n    seq = {add};\n    stride = 1;\n    \n};\n",
+            3,
+        );
+        assert!(completions.contains(&"sequence"));
+        assert!(completions.contains(&"seq"));
+        assert!(!completions.contains(&"stride"));
+    }
+
+    #[test]
+    fn misc_setting_is_repeatable() {
+        assert_eq!(
+            completions(
+                "acl_miscellaneous_setting {\n    misc_setting = yes;\n    \n};\n",
+                2
+            ),
+            ["misc_setting"]
+        );
     }
 }
@@ -1,19 +1,39 @@
 use super::*;
+use nom::bytes::complete::take_while1;
+use nom::character::complete::satisfy;
+use nom::combinator::not;
+use nom::error::ParseError;
+use nom::InputTake;
+use nom_supreme::tag::TagError;
 use paste::paste;
+use std::borrow::Cow;
 
-/// Parses spacing, which consists of either the end of the input ([`eof`])
-/// or a sequence of comments and whitespace characters ([`comment`] or [`multispace1`]).
+/// Parses spacing, which consists of either the end of the input (`eof`) or a sequence
+/// of comments and whitespace characters ([`comment`], [`block_comment`] or [`multispace1`]).
 ///
 /// ## Grammar
 ///
-/// - [`spacing`] <- [`eof`] / ([`comment`] / [`multispace1`])*
-/// - [`eof`]       <- !.
+/// - [`spacing`] <- `eof` / ([`comment`] / [`block_comment`] / [`multispace1`])*
+/// - `eof`       <- !.
 pub fn spacing(
     input: LocatedSpan<&str>,
 ) -> IResult<LocatedSpan<&str>, Vec<LocatedSpan<&str>>, ErrorTree<LocatedSpan<&str>>> {
-    eof.map(|eof| vec![eof])
-        .or(many0(alt((comment, multispace1))))
-        .parse(input)
+    Ok(split_spacing(input))
+}
+
+/// Splits the leading [`spacing`] of `input`, which never fails, and returns the rest
+/// of the input with the spacing.
+pub fn split_spacing(input: LocatedSpan<&str>) -> (LocatedSpan<&str>, Vec<LocatedSpan<&str>>) {
+    if input.is_empty() {
+        return (input, vec![input]);
+    }
+    let mut s = input;
+    let mut spacing = vec![];
+    while let Ok((rest, item)) = alt((comment, block_comment, multispace1)).parse(s) {
+        s = rest;
+        spacing.push(item);
+    }
+    (s, spacing)
 }
 
 /// Parses a comment, which starts with `//` and continues until the end of the line.
@@ -31,6 +51,34 @@ pub fn comment(
         .parse(input)
 }
 
+/// Parses a block comment, which starts with `/*` and continues until the next `*/`,
+/// possibly over several lines. A block comment which is not closed runs until the end
+/// of the input, and is reported by [`recovery::unterminated_comment`].
+///
+/// ## Grammar
+///
+/// - [`block_comment`] <- '/*' (!'*/' .)* '*/'?
+///
+/// ```
+/// # use ace_parser::grammar::token::block_comment;
+/// # use nom_locate::LocatedSpan;
+/// let (rest, comment) = block_comment(LocatedSpan::new("/* a\n   b */ cpu")).unwrap();
+/// assert_eq!(*comment.fragment(), "/* a\n   b */");
+/// assert_eq!(rest.location_line(), 2);
+/// ```
+pub fn block_comment(
+    input: LocatedSpan<&str>,
+) -> IResult<LocatedSpan<&str>, LocatedSpan<&str>, ErrorTree<LocatedSpan<&str>>> {
+    let (s, _) = tag(literal::token::LEFT_MULTILINE_COMMENT).parse(input)?;
+    let length = s
+        .fragment()
+        .find(literal::token::RIGHT_MULTILINE_COMMENT)
+        .map_or(s.len(), |end| {
+            end + literal::token::RIGHT_MULTILINE_COMMENT.len()
+        });
+    Ok(input.take_split(literal::token::LEFT_MULTILINE_COMMENT.len() + length))
+}
+
 /// Parses an end-of-line (EOL) sequence, which can be any of `\r\n`, `\n`, or `\r`.
 ///
 /// ## Grammar
@@ -42,35 +90,237 @@ pub fn end_of_line(
     alt((tag("\r\n"), tag("\r"), tag("\n")))(input)
 }
 
-/// This macro generates a Rust parser for recognizing a 
-/// specific lexical token in a parsing context. It creates a function to instantiate 
-/// the parser, a parser struct with an optional label completion feature, and a struct 
-/// representing the parsed token. The parser follows a specified PEG grammar rule and 
-/// includes spacing handling. The generated parser implements parsing logic, autocompletion, 
+/// Parses a block of verbatim C/C++ code enclosed in `%{` and `%}`.
+///
+/// The block ends at the first `%}` outside of any nested braces, string or character
+/// literal and comment of the enclosed code, so `%{ printf("%}"); %}` is a single block.
+/// A `'` only starts a character literal when it is closed as in `'x'` or `'\n'`, so that
+/// the apostrophe of a Scala symbol `'a` does not swallow the rest of the block.
+/// A block which is not closed, e.g. because of an unbalanced `{`, runs until the end
+/// of the input, and is reported by [`recovery::unterminated_code_block`].
+///
+/// ## Grammar
+///
+/// - [`embedded_code`] <- '%{' code* '%}'?
+/// - code <- '{' (!'}' code)* '}'? / quoted / comment / block_comment / !'%}' .
+/// - quoted <- '"' ('\\' . / !'"' .)* '"'? / "'" ('\\' . .? .? .? / !"'" .) "'"
+///
+/// ```
+/// # use ace_parser::grammar::token::embedded_code;
+/// # use nom_locate::LocatedSpan;
+/// let (rest, block) = embedded_code(LocatedSpan::new("%{ if (a) { b(\"%}\"); } %}; x")).unwrap();
+/// assert_eq!(*block.fragment(), "%{ if (a) { b(\"%}\"); } %}");
+/// assert_eq!(*rest.fragment(), "; x");
+/// ```
+pub fn embedded_code(
+    input: LocatedSpan<&str>,
+) -> IResult<LocatedSpan<&str>, LocatedSpan<&str>, ErrorTree<LocatedSpan<&str>>> {
+    let _ = tag(literal::token::LEFT_PERCENT_BRACE).parse(input)?;
+    let (length, _) = embedded_code_length(input.fragment());
+    Ok(input.take_split(length))
+}
+
+/// Returns the length of the block of code which starts `input` with a `%{`, and
+/// whether it is terminated by a `%}`.
+fn embedded_code_length(input: &str) -> (usize, bool) {
+    let code = input.as_bytes();
+    let mut depth = 0usize;
+    let mut i = literal::token::LEFT_PERCENT_BRACE.len();
+    while i < code.len() {
+        match (code[i], code.get(i + 1)) {
+            (b'%', Some(b'}')) if depth == 0 => {
+                return (i + literal::token::RIGHT_PERCENT_BRACE.len(), true);
+            }
+            (b'{', _) => depth += 1,
+            (b'}', _) => depth = depth.saturating_sub(1),
+            (b'"', _) => {
+                i += 1;
+                while i < code.len() && code[i] != b'"' {
+                    if code[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            (b'\'', _) => i += char_literal_length(&input[i..]).map_or(0, |length| length - 1),
+            (b'/', Some(b'/')) => {
+                while i < code.len() && code[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            (b'/', Some(b'*')) => {
+                i += 2;
+                while i < code.len() && !code[i..].starts_with(b"*/") {
+                    i += 1;
+                }
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    (code.len(), false)
+}
+
+/// Returns the length of the C character literal, e.g. `'x'` or `'\n'`, which starts
+/// `code`, or `None` when the `'` is not closed shortly after on the same line, e.g. in
+/// the Scala symbol `'a`, so that it is an ordinary character.
+fn char_literal_length(code: &str) -> Option<usize> {
+    let mut chars = code.char_indices().skip(1);
+    match chars.next()? {
+        (_, '\'' | '\n') => None,
+        // an escape sequence, from `'\n'` up to `'\x7f'` or `'\177'`
+        (_, '\\') => chars
+            .skip(1)
+            .take(4)
+            .take_while(|(_, c)| *c != '\n')
+            .find(|(_, c)| *c == '\'')
+            .map(|(end, _)| end + 1),
+        _ => chars
+            .next()
+            .filter(|(_, c)| *c == '\'')
+            .map(|(end, _)| end + 1),
+    }
+}
+
+/// Parses the longest of the `keywords` the input starts with, so that the spellings
+/// of a keyword may be listed in any order. A keyword must not be followed by a word
+/// character, so that `vlenx` or `configuration` are not split into a keyword and the
+/// rest of an identifier.
+///
+/// ## Grammar
+///
+/// - [`keyword`] <- (`keywords[0]` / `keywords[1]` / ...) ![`is_word_char`]
+///
+/// ```
+/// # use ace_parser::grammar::token::{identifier, keyword};
+/// # use ace_parser::literal;
+/// # use nom::Parser;
+/// # use nom_locate::LocatedSpan;
+/// let (_, spelling) = keyword(&literal::token::VRF_ALIAS)
+///     .parse(LocatedSpan::new("vrf v1"))
+///     .unwrap();
+/// assert_eq!(*spelling.fragment(), "vrf");
+///
+/// const CSIM: [&str; 1] = [literal::attribute::CSIM];
+/// assert!(keyword(&CSIM).parse(LocatedSpan::new("csim = %{ %};")).is_ok());
+/// assert!(keyword(&CSIM).parse(LocatedSpan::new("csim_header {")).is_err());
+/// assert!(keyword(&CSIM).parse(LocatedSpan::new("csim_cycle = 1;")).is_err());
+/// assert!(keyword(&CSIM).parse(LocatedSpan::new("csim_init = 1;")).is_err());
+///
+/// // the keywords of the statements are not identifiers, but their prefixes are
+/// assert!(identifier().parse(LocatedSpan::new("csim_header {")).is_err());
+/// assert!(identifier().parse(LocatedSpan::new("csim_cycle = 1;")).is_ok());
+/// ```
+pub fn keyword<'a>(
+    keywords: &'static [&'static str],
+) -> impl Parser<LocatedSpan<&'a str>, LocatedSpan<&'a str>, ErrorTree<LocatedSpan<&'a str>>> {
+    move |input: LocatedSpan<&'a str>| match keywords
+        .iter()
+        .filter(|keyword| {
+            input
+                .fragment()
+                .strip_prefix(**keyword)
+                .is_some_and(|rest| !rest.starts_with(is_word_char))
+        })
+        .max_by_key(|keyword| keyword.len())
+    {
+        Some(keyword) => Ok(input.take_split(keyword.len())),
+        None => Err(nom::Err::Error(
+            keywords
+                .iter()
+                .map(|keyword| ErrorTree::from_tag(input, *keyword))
+                .reduce(ParseError::or)
+                .expect("a keyword should have at least one spelling."),
+        )),
+    }
+}
+
+/// Returns whether `c` may appear in an identifier or a keyword.
+pub fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The keywords starting the statements.
+const STATEMENT_KEYWORDS: &[&[&str]] = &[
+    &[literal::statement::CPU, literal::statement::CONFIG],
+    &literal::statement::ACR_ALIAS,
+    &literal::statement::ACM_ALIAS,
+    &[literal::statement::ACP, literal::statement::CSIM_HEADER],
+    &literal::statement::INSTRUCTION_ALIAS,
+    &literal::statement::BACKGROUND_INSTRUCTION_ALIAS,
+    &literal::statement::VECTOR_ALIAS,
+    &literal::statement::UTILITY_INSTRUCTION_ALIAS,
+    &literal::statement::RVV_INSTRUCTION_ALIAS,
+    &[
+        literal::statement::ACL_MISCELLANEOUS_SETTING,
+        literal::statement::TEST_PATTERN,
+        literal::statement::RESOURCE,
+        literal::statement::INSN_GROUP,
+        literal::statement::SYNC,
+        literal::statement::STATUS,
+    ],
+    &literal::statement::TEST_SEQUENCE_ALIAS,
+    &literal::statement::TESTBENCH_SEQUENCE_ALIAS,
+];
+
+/// The keywords which are not [`Identifier`]s: the ones of the statements and of the
+/// element types, which may take the place of an identifier in the grammar.
+const RESERVED_WORDS: &[&[&str]] = &[&[
+    literal::token::UINT,
+    literal::token::INT,
+    literal::token::FP,
+    literal::token::MASK,
+]];
+
+/// Returns whether `word` is one of the keywords starting the statements.
+pub fn is_statement_keyword(word: &str) -> bool {
+    STATEMENT_KEYWORDS.iter().any(|words| words.contains(&word))
+}
+
+/// Returns whether `word` is one of the [`STATEMENT_KEYWORDS`] or [`RESERVED_WORDS`].
+fn is_reserved_word(word: &str) -> bool {
+    is_statement_keyword(word) || RESERVED_WORDS.iter().any(|words| words.contains(&word))
+}
+
+/// This macro generates a Rust parser for recognizing a
+/// specific lexical token in a parsing context. It creates a function to instantiate
+/// the parser, a parser struct with an optional label completion feature, and a struct
+/// representing the parsed token. The parser follows a specified PEG grammar rule and
+/// includes spacing handling. The generated parser implements parsing logic, autocompletion,
 /// and position querying for parsed tokens.
+///
+/// A token which may be malformed, like an unterminated string literal, is given a last
+/// argument returning the syntax error of its parsed token, if any.
 macro_rules! define_lexical_terminal {
-    ($struct_name:ident, $token:expr, $parser:expr, $test_input:literal) => {
+    ($struct_name:ident, $token:expr, $parser:expr, $test_input:literal $(, $syntax_error:expr)?) => {
         paste! {
             #[doc = concat!(
                 " This function returns a [`", stringify!($struct_name), "`], which is a type of [`Parser`] trait.\n\n",
                 " The [`", stringify!([<$struct_name Parser>]), "`] is initialized without any label completion.\n\n",
                 " ## Grammar\n\n",
                 " It's based on the following PEG grammar rule of the **ACE** grammar:\n\n",
-                " - [`", stringify!([<$struct_name:snake>]), "`] <- [`", stringify!($token), "`] [`spacing`]\n\n",
+                " - [`fn@", stringify!([<$struct_name:snake>]), "`] <- [`", stringify!($token), "`] [`spacing`]\n\n",
                 " ## Example Usage\n\n",
                 " To create a parser and use it to parse input:\n\n",
                 " ```\n",
-                " let input = ", stringify!($test_input), ";\n",
-                " let (remained_input, token) = ", stringify!([<$struct_name:snake>]), "().parse(input).unwrap();\n",
+                " # use ace_parser::grammar::token::*;\n",
+                " # use nom::Parser;\n",
+                " # use nom_locate::LocatedSpan;\n",
+                " let source = LocatedSpan::new(", stringify!($test_input), ");\n",
+                " let (remained_input, token) = ", stringify!([<$struct_name:snake>]), "().parse(source).unwrap();\n",
                 " ```\n\n",
                 " To create a parser with a specific label completion:\n\n",
                 " ```\n",
-                " use marker::LabelCompletion;\n\n",
-                " let input = ", stringify!($test_input), ";\n",
-                " let (remained_input, token) = ", stringify!([<$struct_name:snake>]), "().set_label_completion(LabelCompletion::Statement).parse(input).unwrap();\n",
+                " # use ace_parser::grammar::token::*;\n",
+                " # use nom::Parser;\n",
+                " # use nom_locate::LocatedSpan;\n",
+                " use ace_parser::marker::LabelCompletion;\n\n",
+                " let source = LocatedSpan::new(", stringify!($test_input), ");\n",
+                " let (remained_input, token) = ", stringify!([<$struct_name:snake>]), "().set_label_completion(LabelCompletion::Statement).parse(source).unwrap();\n",
                 " ```"
             )]
-            pub fn [<$struct_name:snake>]<'a>() -> [<$struct_name Parser>] {
+            pub fn [<$struct_name:snake>]() -> [<$struct_name Parser>] {
                 [<$struct_name Parser>]::default()
             }
 
@@ -90,7 +340,8 @@ macro_rules! define_lexical_terminal {
                     " This can be used to provide autocompletion hints when parsing.\n\n",
                     " ## Example Usage\n\n",
                     " ```\n",
-                    " use ace::grammar::marker::LabelCompletion;\n",
+                    " # use ace_parser::grammar::token::*;\n",
+                    " use ace_parser::marker::LabelCompletion;\n\n",
                     " let mut parser = ", stringify!([<$struct_name:snake>]), "();\n",
                     " parser.set_label_completion(LabelCompletion::None);\n",
                     " ```"
@@ -101,7 +352,7 @@ macro_rules! define_lexical_terminal {
                 }
             }
 
-            impl<'a, 'b> Parser<LocatedSpan<&'a str>, $struct_name<'a>, ErrorTree<LocatedSpan<&'a str>>> for [<$struct_name Parser>] {
+            impl<'a> Parser<LocatedSpan<&'a str>, $struct_name<'a>, ErrorTree<LocatedSpan<&'a str>>> for [<$struct_name Parser>] {
                 #[doc = concat!(
                     " Parses a regular expression [`", stringify!($struct_name), "`] token from the input.\n\n",
                     "This function attempts to match the regular expression [`", stringify!($token), "`] against the input stream, capturing any trailing spaces. ",
@@ -129,7 +380,7 @@ macro_rules! define_lexical_terminal {
             }
 
             #[doc = concat!(
-                " Represents the [`", stringify!([<$struct_name:snake>]), "`] lexical terminal in the parsing process.\n\n",
+                " Represents the [`fn@", stringify!([<$struct_name:snake>]), "`] lexical terminal in the parsing process.\n\n",
                 " This structure stores information about a parsed [`", stringify!($token), "`] token, including its position in the input, spacing, and optional label completion."
             )]
             #[derive(Debug, Getters)]
@@ -154,6 +405,11 @@ macro_rules! define_lexical_terminal {
             }
 
             impl ParseTree for $struct_name<'_> {
+                #[doc = " Returns the kind of this token."]
+                fn node_kind(&self) -> NodeKind {
+                    NodeKind::Token(stringify!([<$struct_name:snake>]))
+                }
+
                 #[doc = concat!(
                     " Returns the range of this regular expression [`", stringify!($struct_name), "`] token in the parsed input."
                 )]
@@ -161,26 +417,29 @@ macro_rules! define_lexical_terminal {
                     self.range
                 }
 
-                #[doc = concat!(
-                    " Queries whether the given position is within this regular expression [`", stringify!($struct_name), "`] token's range.\n\n",
-                    " Returns `Ok(self)` if the position is within the range, otherwise `Err(())`."
-                )]
-                fn query(&self, pos: Position) -> Result<&dyn ParseTree, ()> {
-                    if self.range().contains(pos) {
-                        Ok(self)
-                    } else {
-                        Err(())
-                    }
+                #[doc = " Returns the error of this token if it is malformed, or else the one of the unterminated block comment which ends its spacing, if any."]
+                fn syntax_error(&self) -> Option<recovery::SyntaxError> {
+                    $(
+                        if let Some(error) = ($syntax_error)(self.token) {
+                            return Some(error);
+                        }
+                    )?
+                    recovery::unterminated_comment(&self.spacing)
+                }
+
+                #[doc = " Returns the label completion associated with this token."]
+                fn label_completion(&self) -> marker::LabelCompletion {
+                    self.label_completion
                 }
 
                 #[doc = concat!(
                     " Returns a list of possible autocompletion suggestions for this token.\n\n",
-                    " The autocompletion suggestions are based on the [`label_completion`] associated with this token.\n\n",
+                    " The autocompletion suggestions are based on the [`ParseTree::label_completion`] associated with this token.\n\n",
                     " ## Example Usage\n\n",
-                    " ```\n",
-                    " use marker::LabelCompletion;\n\n",
-                    " let input = ", stringify!($test_input), ";\n",
-                    " let (remained_input, token) = ", stringify!([<$struct_name:snake>]), "().set_label_completion(LabelCompletion::Statement).parse(input).unwrap();\n",
+                    " ```ignore\n",
+                    " use ace_parser::marker::LabelCompletion;\n\n",
+                    " let source = LocatedSpan::new(", stringify!($test_input), ");\n",
+                    " let (remained_input, token) = ", stringify!([<$struct_name:snake>]), "().set_label_completion(LabelCompletion::Statement).parse(source).unwrap();\n",
                     " let completions = token.show_completions();\n",
                     " println!(\"Suggested completions: {:?}\", completions);\n",
                     " ```"
@@ -193,36 +452,42 @@ macro_rules! define_lexical_terminal {
     };
 }
 
-define_lexical_terminal!(
-    Cpu,
-    literal::statement::CPU,
-    tag(literal::statement::CPU),
-    "cpu \n"
-);
-define_lexical_terminal!(
-    Config,
-    literal::statement::CONFIG,
-    tag(literal::statement::CONFIG),
-    "config \n"
-);
-define_lexical_terminal!(
+/// This macro generates a lexical terminal of a keyword, which is spelled as any of the
+/// strings of the array `$keywords`, the first one being its canonical spelling. Besides
+/// the items of [`define_lexical_terminal`], it implements `canonical` and `spelling` on
+/// the token so that tools can normalise or preserve the spelling of the user.
+macro_rules! define_keyword {
+    ($struct_name:ident, $keywords:expr, $test_input:literal) => {
+        define_lexical_terminal!($struct_name, $keywords, keyword(&$keywords), $test_input);
+
+        impl<'a> $struct_name<'a> {
+            /// Returns the canonical spelling of this keyword.
+            pub fn canonical(&self) -> &'static str {
+                $keywords[0]
+            }
+
+            /// Returns the spelling of this keyword in the parsed input.
+            pub fn spelling(&self) -> &'a str {
+                self.token.fragment()
+            }
+
+            /// Returns every spelling of this keyword, the canonical one first.
+            pub fn aliases(&self) -> &'static [&'static str] {
+                &$keywords
+            }
+        }
+    };
+}
+
+define_keyword!(Cpu, [literal::statement::CPU], "cpu \n");
+define_keyword!(Config, [literal::statement::CONFIG], "config \n");
+define_keyword!(
     TimeoutCycle,
-    literal::attribute::TIMEOUT_CYCLE,
-    tag(literal::attribute::TIMEOUT_CYCLE),
+    [literal::attribute::TIMEOUT_CYCLE],
     "timeout_cycle \n"
 );
-define_lexical_terminal!(
-    Name,
-    literal::attribute::NAME,
-    tag(literal::attribute::NAME),
-    "name \n"
-);
-define_lexical_terminal!(
-    Vlen,
-    literal::attribute::VLEN,
-    tag(literal::attribute::VLEN),
-    "vlen \n"
-);
+define_keyword!(Name, [literal::attribute::NAME], "name \n");
+define_keyword!(Vlen, [literal::attribute::VLEN], "vlen \n");
 define_lexical_terminal!(
     LeftBrace,
     literal::token::LEFT_BRACE,
@@ -250,24 +515,713 @@ define_lexical_terminal!(
 define_lexical_terminal!(
     Identifier,
     literal::token::IDENTIFIER,
-    take_while(|c: char| c.is_alphabetic() || c == '_')
-        .and(take_while(|c: char| c.is_alphanumeric() || c == '_'))
-        .recognize(),
+    take_while1(|c: char| c.is_alphabetic() || c == '_')
+        .and(take_while(is_word_char))
+        .recognize()
+        .verify(|s: &LocatedSpan<&str>| !is_reserved_word(s.fragment()))
+        .context("identifier"),
     "NX45V \n"
 );
 define_lexical_terminal!(
     HexNumber,
     literal::token::HEX_NUMBER,
     tag_no_case("0x")
-        .and(take_while(|c: char| c.is_ascii_hexdigit()))
-        .recognize(),
+        .and(take_while1(|c: char| c.is_ascii_hexdigit()))
+        .and(not(satisfy(is_word_char)))
+        .recognize()
+        .context("hexadecimal number"),
     "0x1234 \n"
 );
+define_lexical_terminal!(
+    BinNumber,
+    literal::token::BIN_NUMBER,
+    tag_no_case("0b")
+        .and(take_while1(|c: char| c == '0' || c == '1'))
+        .and(not(satisfy(is_word_char)))
+        .recognize()
+        .context("binary number"),
+    "0b1010 \n"
+);
+define_lexical_terminal!(
+    SizedNumber,
+    literal::token::SIZED_NUMBER,
+    take_while1(|c: char| c.is_ascii_digit())
+        .verify(|s: &LocatedSpan<&str>| !s.starts_with('0'))
+        .and(tag("'"))
+        .and(
+            take_while1(is_word_char)
+                .verify(|s: &LocatedSpan<&str>| sized_number_radix(s.fragment()).is_some()),
+        )
+        .recognize()
+        .context("sized number"),
+    "32'hFF \n"
+);
+
+impl<'a> SizedNumber<'a> {
+    /// Returns the width in bits of this number, e.g. `32` for `32'hFF`.
+    pub fn size(&self) -> &'a str {
+        let (size, _) = self
+            .token
+            .fragment()
+            .split_once('\'')
+            .expect("sized number has a `'`");
+        size
+    }
+
+    /// Returns the radix of the digits of this number, e.g. `16` for `32'hFF`.
+    pub fn radix(&self) -> u32 {
+        let (_, value) = self
+            .token
+            .fragment()
+            .split_once('\'')
+            .expect("sized number has a `'`");
+        sized_number_radix(value).expect("sized number has a base")
+    }
+
+    /// Returns the digits of this number, e.g. `FF` for `32'hFF`.
+    pub fn digits(&self) -> &'a str {
+        let (_, digits) = self
+            .token
+            .fragment()
+            .split_once('\'')
+            .expect("sized number has a `'`");
+        &digits[1..]
+    }
+}
+
+/// Returns the radix of the value of a Verilog-style sized number, like `hFF` for
+/// `32'hFF`, or `None` if its base is unknown or its digits are not valid in that base.
+fn sized_number_radix(value: &str) -> Option<u32> {
+    let mut chars = value.chars();
+    let radix = match chars.next()?.to_ascii_lowercase() {
+        'b' => 2,
+        'o' => 8,
+        'd' => 10,
+        'h' => 16,
+        _ => return None,
+    };
+    let digits = chars.as_str();
+    (!digits.is_empty()
+        && !digits.starts_with('_')
+        && digits.chars().all(|c| c == '_' || c.is_digit(radix)))
+    .then_some(radix)
+}
+
 define_lexical_terminal!(
     DecNumber,
     literal::token::DEC_NUMBER,
-    take_while(|c: char| c.is_ascii_digit())
+    take_while1(|c: char| c.is_ascii_digit())
         .verify(|s: &LocatedSpan<&str>| !s.starts_with('0'))
-        .recognize(),
+        .and(not(satisfy(|c| is_word_char(c) || c == '\'')))
+        .recognize()
+        .context("decimal number"),
     "1234 \n"
 );
+define_lexical_terminal!(
+    Zero,
+    literal::token::ZERO,
+    keyword(&[literal::token::ZERO]).context("decimal number"),
+    "0 \n"
+);
+define_lexical_terminal!(
+    NegativeSign,
+    literal::token::NEGATIVE_SIGN,
+    tag(literal::token::NEGATIVE_SIGN),
+    "- \n"
+);
+define_keyword!(Register, literal::statement::ACR_ALIAS, "reg \n");
+define_keyword!(Width, [literal::attribute::WIDTH], "width \n");
+define_keyword!(Num, literal::attribute::NUMBER_ALIASES, "num \n");
+define_keyword!(Reset, [literal::attribute::RESET], "reset \n");
+define_keyword!(
+    ResetDefault,
+    [literal::attribute::RESET_DEFAULT],
+    "reset_default \n"
+);
+define_keyword!(Ram, [literal::statement::ACM_ALIAS[0]], "ram \n");
+define_keyword!(Rom, [literal::statement::ACM_ALIAS[1]], "rom \n");
+define_keyword!(Interface, [literal::attribute::INTERFACE], "interface \n");
+define_keyword!(Latency, [literal::attribute::LATENCY], "latency \n");
+define_keyword!(
+    ByteEnable,
+    literal::attribute::BYTE_ENABLE_ALIASES,
+    "byte_enable \n"
+);
+define_keyword!(
+    WriteStrobe,
+    [literal::attribute::WRITE_STROBE],
+    "write_strobe \n"
+);
+define_keyword!(
+    MaxBurstLength,
+    [literal::attribute::MAX_BURST_LENGTH],
+    "max_burst_length \n"
+);
+define_keyword!(
+    ErrorDetect,
+    [literal::attribute::ERROR_DETECT],
+    "error_detect \n"
+);
+define_keyword!(Content, [literal::attribute::CONTENT], "content \n");
+define_keyword!(
+    ContentDefault,
+    [literal::attribute::CONTENT_DEFAULT],
+    "content_default \n"
+);
+define_keyword!(Yes, [literal::token::YES], "yes \n");
+define_keyword!(No, [literal::token::NO], "no \n");
+define_keyword!(Sram, [literal::token::SRAM], "sram \n");
+define_keyword!(Ahb, [literal::token::AHB], "ahb \n");
+define_keyword!(Axi, [literal::token::AXI], "axi \n");
+define_keyword!(Port, [literal::statement::ACP], "port \n");
+define_keyword!(IoType, [literal::attribute::IO_TYPE], "io_type \n");
+define_keyword!(
+    ExportLevel,
+    [literal::attribute::EXPORT_LEVEL],
+    "export_level \n"
+);
+define_keyword!(
+    AccessType,
+    [literal::attribute::ACCESS_TYPE],
+    "access_type \n"
+);
+define_keyword!(Input, [literal::token::IN], "in \n");
+define_keyword!(Output, [literal::token::OUT], "out \n");
+define_keyword!(InOut, [literal::token::IO], "io \n");
+define_keyword!(Input2, [literal::token::IN2], "in2 \n");
+define_keyword!(Output2, [literal::token::OUT2], "out2 \n");
+define_keyword!(InOut2, [literal::token::IO2], "io2 \n");
+define_keyword!(Read, [literal::token::READ], "read \n");
+define_keyword!(Write, [literal::token::WRITE], "write \n");
+define_keyword!(Access, [literal::token::ACCESS], "access \n");
+define_keyword!(
+    Instruction,
+    literal::statement::INSTRUCTION_ALIAS,
+    "insn \n"
+);
+define_keyword!(Operand, literal::attribute::OPERAND_ALIASES, "op \n");
+define_keyword!(
+    ImpliedOperand,
+    literal::attribute::IMPLIED_OPERAND_ALIASES,
+    "implied_op \n"
+);
+define_keyword!(
+    CsrOperand,
+    literal::attribute::CSR_OPERAND_ALIASES,
+    "csr_op \n"
+);
+define_keyword!(
+    InsnEncode,
+    [literal::attribute::INSN_ENCODE],
+    "insn_encode \n"
+);
+define_keyword!(Blocking, [literal::attribute::BLOCKING], "blocking \n");
+define_keyword!(
+    SideEffect,
+    [literal::attribute::SIDE_EFFECT],
+    "side_effect \n"
+);
+define_keyword!(Csim, [literal::attribute::CSIM], "csim \n");
+define_keyword!(Chisel, [literal::attribute::CHISEL], "chisel \n");
+define_keyword!(Spinalhdl, [literal::attribute::SPINALHDL], "spinalhdl \n");
+define_lexical_terminal!(
+    Comma,
+    literal::token::COMMA,
+    tag(literal::token::COMMA),
+    ", \n"
+);
+define_lexical_terminal!(
+    CodeBlock,
+    literal::token::LEFT_PERCENT_BRACE,
+    embedded_code,
+    "%{ return 0; %} \n",
+    |token: LocatedSpan<&str>| {
+        (!embedded_code_length(token.fragment()).1)
+            .then(|| recovery::unterminated_code_block(token))
+    }
+);
+
+impl<'a> CodeBlock<'a> {
+    /// Returns the verbatim code between `%{` and `%}`.
+    pub fn code(&self) -> &'a str {
+        let block = self.token.fragment();
+        let end = if self.is_terminated() {
+            block.len() - literal::token::RIGHT_PERCENT_BRACE.len()
+        } else {
+            block.len()
+        };
+        &block[literal::token::LEFT_PERCENT_BRACE.len()..end]
+    }
+
+    /// Returns whether the block is closed by `%}`.
+    pub fn is_terminated(&self) -> bool {
+        embedded_code_length(self.token.fragment()).1
+    }
+}
+
+define_keyword!(
+    CsimHeader,
+    [literal::statement::CSIM_HEADER],
+    "csim_header \n"
+);
+define_keyword!(Xrf, literal::token::XRF_ALIAS, "gpr \n");
+define_keyword!(Frf, literal::token::FRF_ALIAS, "fpr \n");
+define_keyword!(Vrf, literal::token::VRF_ALIAS, "vr \n");
+define_lexical_terminal!(
+    Memory,
+    literal::token::MEMORIES,
+    take_while1(is_word_char)
+        .verify(|s: &LocatedSpan<&str>| literal::token::MEMORIES.contains(s.fragment()))
+        .context("memory"),
+    "mem32 \n"
+);
+define_keyword!(Uint, [literal::token::UINT], "uint \n");
+define_keyword!(Int, [literal::token::INT], "int \n");
+define_keyword!(Fp, [literal::token::FP], "fp \n");
+define_keyword!(Mask, [literal::token::MASK], "mask \n");
+define_keyword!(
+    DoubleWidthUint,
+    [literal::token::DOUBLE_WIDTH_UINT],
+    "2uint \n"
+);
+define_keyword!(
+    DoubleWidthInt,
+    [literal::token::DOUBLE_WIDTH_INT],
+    "2int \n"
+);
+define_keyword!(DoubleWidthFp, [literal::token::DOUBLE_WIDTH_FP], "2fp \n");
+define_lexical_terminal!(
+    Immediate,
+    literal::token::IMMEDIATES,
+    take_while1(is_word_char)
+        .verify(|s: &LocatedSpan<&str>| literal::token::IMMEDIATES.contains(s.fragment()))
+        .context("immediate"),
+    "imm12 \n"
+);
+define_lexical_terminal!(
+    Function,
+    literal::token::FUNCTIONS,
+    take_while1(is_word_char)
+        .verify(|s: &LocatedSpan<&str>| literal::token::FUNCTIONS.contains(s.fragment()))
+        .context("function"),
+    "func3 \n"
+);
+define_keyword!(
+    VectorInstruction,
+    literal::statement::VECTOR_ALIAS,
+    "vec \n"
+);
+define_keyword!(
+    RvvInstruction,
+    literal::statement::RVV_INSTRUCTION_ALIAS,
+    "rvv_insn \n"
+);
+define_keyword!(
+    VectorMask,
+    [literal::attribute::VECTOR_MASK],
+    "vector_mask \n"
+);
+define_keyword!(
+    VectorUnit,
+    [literal::attribute::VECTOR_UNIT],
+    "vector_unit \n"
+);
+define_keyword!(
+    RvvCustomKill,
+    [literal::attribute::RVV_CUSTOM_KILL],
+    "rvv_custom_kill \n"
+);
+define_keyword!(
+    BackgroundInstruction,
+    literal::statement::BACKGROUND_INSTRUCTION_ALIAS,
+    "bg_insn \n"
+);
+define_keyword!(
+    UtilityInstruction,
+    literal::statement::UTILITY_INSTRUCTION_ALIAS,
+    "u_insn \n"
+);
+define_keyword!(Utility, literal::attribute::UTILITY_ALIASES, "util \n");
+define_keyword!(
+    UtilityKind,
+    [literal::attribute::UTILITY_KIND],
+    "utility_kind \n"
+);
+define_keyword!(
+    OutstandingInsnNum,
+    [literal::attribute::OUTSTANDING_INSN_NUM],
+    "outstanding_insn_num \n"
+);
+define_keyword!(Interrupt, [literal::attribute::INTERRUPT], "interrupt \n");
+define_keyword!(RdUtil, [literal::token::RD_UTIL], "rd_util \n");
+define_keyword!(WrUtil, [literal::token::WR_UTIL], "wr_util \n");
+define_keyword!(RdInsn, [literal::token::RD_INSN], "rd_insn \n");
+define_keyword!(WrInsn, [literal::token::WR_INSN], "wr_insn \n");
+define_keyword!(Background, [literal::token::BACKGROUND], "background \n");
+define_keyword!(Foreground, [literal::token::FOREGROUND], "foreground \n");
+define_keyword!(Dlen, [literal::attribute::DLEN], "dlen \n");
+define_keyword!(Elen, [literal::attribute::ELEN], "elen \n");
+define_keyword!(Flen, [literal::attribute::FLEN], "flen \n");
+define_keyword!(Felen, [literal::attribute::FELEN], "felen \n");
+define_keyword!(
+    StreamingPortWidth,
+    [literal::attribute::STREAMING_PORT_WIDTH],
+    "streaming_port_width \n"
+);
+define_keyword!(
+    AddressBits,
+    [literal::attribute::ADDRESS_BITS],
+    "address_bits \n"
+);
+define_keyword!(Endian, [literal::attribute::ENDIAN], "endian \n");
+define_keyword!(March, [literal::attribute::MARCH], "march \n");
+define_keyword!(Big, [literal::token::BIG], "big \n");
+define_keyword!(Little, [literal::token::LITTLE], "little \n");
+define_lexical_terminal!(
+    StringLiteral,
+    literal::token::STRING_LITERAL,
+    string_literal_token.context("string literal"),
+    "\"rv64gcv\" \n",
+    |token: LocatedSpan<&str>| {
+        (!string_literal_length(token.fragment()).1).then(|| recovery::unterminated_string(token))
+    }
+);
+
+impl<'a> StringLiteral<'a> {
+    /// Returns the text between the quotes as it is written, with its escape sequences.
+    pub fn raw(&self) -> &'a str {
+        let token = self.token.fragment();
+        let end = if self.is_terminated() {
+            token.len() - literal::token::QUOTE.len()
+        } else {
+            token.len()
+        };
+        &token[literal::token::QUOTE.len()..end]
+    }
+
+    /// Returns the text between the quotes, whose escape sequences `\"`, `\\` and `\n`
+    /// are replaced by the characters they stand for. Other escape sequences are kept.
+    ///
+    /// ```
+    /// # use ace_parser::grammar::token::string_literal;
+    /// # use nom::Parser;
+    /// # use nom_locate::LocatedSpan;
+    /// let (_, string) = string_literal().parse(LocatedSpan::new(r#""a \"b\"\n\\" "#)).unwrap();
+    /// assert_eq!(string.raw(), r#"a \"b\"\n\\"#);
+    /// assert_eq!(string.value(), "a \"b\"\n\\");
+    /// ```
+    pub fn value(&self) -> Cow<'a, str> {
+        let raw = self.raw();
+        if !raw.contains('\\') {
+            return Cow::Borrowed(raw);
+        }
+        let mut value = String::with_capacity(raw.len());
+        let mut chars = raw.chars();
+        while let Some(c) = chars.next() {
+            match (c, chars.clone().next()) {
+                ('\\', Some(escaped @ ('"' | '\\'))) => {
+                    value.push(escaped);
+                    chars.next();
+                }
+                ('\\', Some('n')) => {
+                    value.push('\n');
+                    chars.next();
+                }
+                (c, _) => value.push(c),
+            }
+        }
+        Cow::Owned(value)
+    }
+
+    /// Returns whether the string is closed by a `"` on its line.
+    pub fn is_terminated(&self) -> bool {
+        string_literal_length(self.token.fragment()).1
+    }
+}
+
+/// Parses a string literal, which runs until the next unescaped `"`, or until the end
+/// of the line if it is not terminated.
+///
+/// ## Grammar
+///
+/// - [`string_literal_token`] <- '"' ('\\' !end_of_line . / !'"' !end_of_line .)* '"'?
+fn string_literal_token(
+    input: LocatedSpan<&str>,
+) -> IResult<LocatedSpan<&str>, LocatedSpan<&str>, ErrorTree<LocatedSpan<&str>>> {
+    let _ = tag(literal::token::QUOTE).parse(input)?;
+    let (length, _) = string_literal_length(input.fragment());
+    Ok(input.take_split(length))
+}
+
+/// Returns the length of the string literal which starts `input` with a `"`, and
+/// whether it is terminated by a `"`.
+fn string_literal_length(input: &str) -> (usize, bool) {
+    let mut chars = input.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return (i + 1, true),
+            '\n' | '\r' => return (i, false),
+            '\\' => {
+                if let Some((i, '\n' | '\r')) = chars.clone().next() {
+                    return (i, false);
+                }
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+    (input.len(), false)
+}
+
+define_keyword!(
+    OptimizationPolicy,
+    literal::attribute::OPTIMIZATION_POLICY_ALIASES,
+    "opt_policy \n"
+);
+define_keyword!(LmLatency, [literal::attribute::LM_LATENCY], "lm_latency \n");
+define_keyword!(
+    BusLatency,
+    [literal::attribute::BUS_LATENCY],
+    "bus_latency \n"
+);
+define_keyword!(
+    ExportLevelAhb,
+    [literal::attribute::EXPORT_LEVEL_AHB],
+    "export_level_ahb \n"
+);
+define_keyword!(
+    ExportLevelAxi,
+    [literal::attribute::EXPORT_LEVEL_AXI],
+    "export_level_axi \n"
+);
+define_keyword!(
+    ExportLevelSram,
+    [literal::attribute::EXPORT_LEVEL_SRAM],
+    "export_level_sram \n"
+);
+define_keyword!(
+    ExportLevelPort,
+    [literal::attribute::EXPORT_LEVEL_PORT],
+    "export_level_port \n"
+);
+define_keyword!(
+    ExportLevelStreamingPort,
+    [literal::attribute::EXPORT_LEVEL_STREAMING_PORT],
+    "export_level_streaming_port \n"
+);
+define_keyword!(RfBuffer, literal::attribute::RF_BUFFER_ALIASES, "rf_buf \n");
+define_keyword!(GprBuffer, [literal::attribute::GPR_BUFFER], "gpr_buffer \n");
+define_keyword!(FrfBuffer, [literal::attribute::FRF_BUFFER], "frf_buffer \n");
+define_keyword!(
+    CustomErrorBits,
+    [literal::attribute::CUSTOM_ERROR_BITS],
+    "custom_error_bits \n"
+);
+define_keyword!(
+    CustomErrorEn,
+    [literal::attribute::CUSTOM_ERROR_EN],
+    "custom_error_en \n"
+);
+define_keyword!(InsnQueue, [literal::attribute::INSN_QUEUE], "insn_queue \n");
+define_keyword!(
+    GroupInBuffer,
+    [literal::attribute::GROUP_IN_BUFFER],
+    "group_in_buffer \n"
+);
+define_keyword!(
+    GroupOutBuffer,
+    [literal::attribute::GROUP_OUT_BUFFER],
+    "group_out_buffer \n"
+);
+define_keyword!(ErrorPc, [literal::attribute::ERROR_PC], "error_pc \n");
+define_keyword!(
+    ClockDomainCrossingStage,
+    [literal::attribute::CLOCK_DOMAIN_CROSSING_STAGE],
+    "clock_domain_crossing_stage \n"
+);
+define_keyword!(Frequency, [literal::token::FREQUENCY], "frequency \n");
+define_keyword!(Auto, [literal::token::AUTO], "auto \n");
+define_keyword!(Direct, [literal::token::DIRECT], "direct \n");
+define_keyword!(
+    IncludeDirective,
+    [literal::token::INCLUDE_DIRECTIVE],
+    "#include \n"
+);
+define_keyword!(
+    TestPattern,
+    [literal::statement::TEST_PATTERN],
+    "test_pattern \n"
+);
+define_keyword!(
+    TestSequence,
+    literal::statement::TEST_SEQUENCE_ALIAS,
+    "test_seq \n"
+);
+define_keyword!(
+    TestbenchSequence,
+    literal::statement::TESTBENCH_SEQUENCE_ALIAS,
+    "tb_seq \n"
+);
+define_keyword!(Sequence, literal::attribute::SEQUENCE_ALIASES, "seq \n");
+define_keyword!(List, [literal::attribute::LIST], "list \n");
+define_keyword!(LoopType, [literal::attribute::LOOP_TYPE], "loop_type \n");
+define_keyword!(Stride, [literal::attribute::STRIDE], "stride \n");
+define_keyword!(
+    TestSequenceInit,
+    literal::attribute::TEST_SEQUENCE_INIT,
+    "seq_init \n"
+);
+define_keyword!(Repeat, [literal::token::REPEAT], "repeat \n");
+define_keyword!(DoWhile, [literal::token::DO_WHILE], "do_while \n");
+define_keyword!(AnyInsn, [literal::token::ANY_INSN], "ANY_INSN \n");
+define_keyword!(BgSync, [literal::token::BG_SYNC], "BG_SYNC \n");
+define_keyword!(SequenceSync, [literal::token::SYNC], "SYNC \n");
+define_keyword!(InsnGroup, [literal::statement::INSN_GROUP], "insn_group \n");
+define_keyword!(Sync, [literal::statement::SYNC], "sync \n");
+define_keyword!(Status, [literal::statement::STATUS], "status \n");
+define_keyword!(Resource, [literal::statement::RESOURCE], "resource \n");
+define_keyword!(Nbsync, [literal::token::NBSYNC], "nbsync \n");
+define_keyword!(Bsync, [literal::token::BSYNC], "bsync \n");
+define_keyword!(StStatus, [literal::token::ST_STATUS], "st_status \n");
+define_keyword!(ErrorRd, [literal::token::ERROR_RD], "error_rd \n");
+define_keyword!(Shared, [literal::token::SHARED], "shared \n");
+define_keyword!(HartId, [literal::token::HART_ID], "hart_id \n");
+define_keyword!(
+    AclMiscellaneousSetting,
+    [literal::statement::ACL_MISCELLANEOUS_SETTING],
+    "acl_miscellaneous_setting \n"
+);
+define_keyword!(
+    MiscSetting,
+    [literal::attribute::MISC_SETTING],
+    "misc_setting \n"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_code_skips_nested_braces() {
+        let source = "%{ if (a) { b(); } else { c(); } %}; x";
+        let (rest, block) = code_block().parse(LocatedSpan::new(source)).unwrap();
+        assert_eq!(block.code(), " if (a) { b(); } else { c(); } ");
+        assert!(block.is_terminated());
+        assert_eq!(*rest.fragment(), "; x");
+    }
+
+    #[test]
+    fn embedded_code_skips_closing_in_strings_and_comments() {
+        let source = "%{ puts(\"%}\"); c = '}'; // %}\n /* %} */ %}";
+        let (rest, block) = code_block().parse(LocatedSpan::new(source)).unwrap();
+        assert_eq!(*block.token().fragment(), source);
+        assert!(rest.fragment().is_empty());
+    }
+
+    #[test]
+    fn unclosed_apostrophe_is_not_a_character_literal() {
+        let source = "%{ x = 'a; y = '\\x7f'; z = '%'; %}; x";
+        let (rest, block) = code_block().parse(LocatedSpan::new(source)).unwrap();
+        assert_eq!(block.code(), " x = 'a; y = '\\x7f'; z = '%'; ");
+        assert!(block.is_terminated());
+        assert_eq!(*rest.fragment(), "; x");
+
+        let ace = ace("insn a { csim = %{ x = 'a; %}; };\ncpu { };\n");
+        assert!(ace.errors().is_empty(), "{:?}", ace.errors());
+        assert_eq!(ace.statements().len(), 2);
+    }
+
+    #[test]
+    fn unbalanced_brace_reports_unterminated_code_block() {
+        let ace = ace("insn add {\n    csim = %{ if (a) { b(); %};\n};\n");
+        let error = &ace.errors()[0];
+        assert_eq!(error.message(), "unterminated code block, expected `%}`");
+        assert_eq!(error.kind(), &recovery::SyntaxErrorKind::Missing);
+    }
+
+    /// Returns the node kinds of the statements of `source`, and the input found at
+    /// each of its errors.
+    fn parse(source: &str) -> (Vec<NodeKind>, Vec<String>) {
+        let ace = ace(source);
+        let statements = ace.statements().iter().map(|s| s.node_kind()).collect();
+        let found = ace
+            .errors()
+            .iter()
+            .map(|error| error.found().clone().unwrap_or_default())
+            .collect();
+        (statements, found)
+    }
+
+    #[test]
+    fn keywords_sharing_a_prefix_are_told_apart() {
+        let (statements, found) = parse(
+            "csim_header %{ int x; %};\ninsn add {\n    csim = %{ a(); %};\n    csim_cycle = 1;\n    csim_init = %{ b(); %};\n};\n",
+        );
+        assert_eq!(
+            statements,
+            [
+                NodeKind::Statement("csim_header"),
+                NodeKind::Statement("instruction")
+            ]
+        );
+        assert_eq!(found, ["`csim_cycle`", "`csim_init`"]);
+
+        let (statements, found) = parse("config {\n};\nconfiguration {\n};\n");
+        assert_eq!(statements, [NodeKind::Statement("config"), NodeKind::Error]);
+        assert_eq!(found, ["`configuration`"]);
+    }
+
+    #[test]
+    fn attribute_key_extending_a_keyword_is_unknown() {
+        let ace = ace("cpu {\n    vlenx = 3;\n    vlen = 512;\n};\n");
+        let [error] = ace.errors().as_slice() else {
+            panic!("expected one error, found {:?}", ace.errors());
+        };
+        assert_eq!(error.kind(), &recovery::SyntaxErrorKind::Unexpected);
+        assert_eq!(error.found().as_deref(), Some("`vlenx`"));
+        assert_eq!(
+            error.range(),
+            &Range {
+                start: Position {
+                    line: 1,
+                    character: 4
+                },
+                end: Position {
+                    line: 2,
+                    character: 4
+                },
+            }
+        );
+
+        let (statements, found) =
+            parse("cpu {\n    name = name_of_thing;\n    name_of_thing = 1;\n};\n");
+        assert_eq!(statements, [NodeKind::Statement("cpu")]);
+        assert_eq!(found, ["`name_of_thing`"]);
+    }
+
+    #[test]
+    fn reserved_words_are_not_identifiers() {
+        let (statements, found) = parse(
+            "insn cpu {\n};\ninsn uint {\n};\ninsn csim_header {\n};\ninsn name_of_thing {\n};\n\
+             insn csim {\n};\n",
+        );
+        assert_eq!(
+            statements,
+            [
+                NodeKind::Error,
+                NodeKind::Error,
+                NodeKind::Error,
+                NodeKind::Statement("instruction"),
+                NodeKind::Statement("instruction")
+            ]
+        );
+        assert_eq!(found, ["`cpu`", "`uint`", "`csim_header`"]);
+        for word in ["config", "register", "insn_group", "fp", "mask"] {
+            assert!(
+                identifier().parse(LocatedSpan::new(word)).is_err(),
+                "{word}"
+            );
+        }
+        assert!(identifier().parse(LocatedSpan::new("csim_cycle")).is_ok());
+    }
+}
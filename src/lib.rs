@@ -7,4 +7,5 @@
 pub mod grammar;
 pub mod literal;
 pub mod marker;
-pub mod snippet;
\ No newline at end of file
+pub mod snippet;
+pub mod workspace;
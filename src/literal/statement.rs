@@ -3,7 +3,6 @@ pub const CONFIG: &str = "config";
 pub const ACR_ALIAS: [&str; 2] = ["register", "reg"];
 pub const ACM_ALIAS: [&str; 2] = ["ram", "rom"];
 pub const ACP: &str = "port";
-pub const CSIM: &str = "csim";
 pub const CSIM_HEADER: &str = "csim_header";
 pub const INSTRUCTION_ALIAS: [&str; 2] = ["instruction", "insn"];
 pub const BACKGROUND_INSTRUCTION_ALIAS: [&str; 2] = ["background_instruction", "bg_insn"];
@@ -19,6 +19,9 @@ pub const AT: &str = "@";
 pub const HASH: &str = "#";
 pub const INCLUDE_DIRECTIVE: &str = "#include";
 pub const QUOTE: &str = "\"";
+/// A string literal stops at the end of its line, and one which is not closed there
+/// is reported as a missing `"`.
+pub const STRING_LITERAL: &str = "'\"' ('\\\\' !end_of_line . / !'\"' !end_of_line .)* '\"'?";
 pub const WHITESPACE: &str = " \n\t\r";
 pub const LEFT_MULTILINE_COMMENT: &str = "/*";
 pub const RIGHT_MULTILINE_COMMENT: &str = "*/";
@@ -31,6 +34,8 @@ pub const NEGATIVE_SIGN: &str = "-";
 pub const IDENTIFIER: &str = "[a-zA-Z_][a-zA-Z0-9_]*";
 pub const HEX_NUMBER: &str = "'0x' [0-9a-fA-F]+";
 pub const DEC_NUMBER: &str = "[1-9][0-9]*";
+pub const BIN_NUMBER: &str = "'0b' [01]+";
+pub const SIZED_NUMBER: &str = "[1-9][0-9]* \"'\" [bBoOdDhH] [0-9a-fA-F_]+";
 pub const YES: &str = "yes";
 pub const NO: &str = "no";
 pub const RESUME: &str = "resume";
@@ -81,6 +86,7 @@ pub const MEM8: &str = "mem8";
 pub const MEM16: &str = "mem16";
 pub const MEM32: &str = "mem32";
 pub const MEM64: &str = "mem64";
+pub const MEMORIES: [&str; 4] = [MEM8, MEM16, MEM32, MEM64];
 pub const UINT: &str = "uint";
 pub const INT: &str = "int";
 pub const FP: &str = "fp";
@@ -113,6 +119,10 @@ pub const IMM22: &str = "imm22";
 pub const IMM23: &str = "imm23";
 pub const IMM24: &str = "imm24";
 pub const IMM25: &str = "imm25";
+pub const IMMEDIATES: [&str; 25] = [
+    IMM1, IMM2, IMM3, IMM4, IMM5, IMM6, IMM7, IMM8, IMM9, IMM10, IMM11, IMM12, IMM13, IMM14, IMM15,
+    IMM16, IMM17, IMM18, IMM19, IMM20, IMM21, IMM22, IMM23, IMM24, IMM25,
+];
 pub const FUNC1: &str = "func1";
 pub const FUNC2: &str = "func2";
 pub const FUNC3: &str = "func3";
@@ -138,6 +148,10 @@ pub const FUNC22: &str = "func22";
 pub const FUNC23: &str = "func23";
 pub const FUNC24: &str = "func24";
 pub const FUNC25: &str = "func25";
+pub const FUNCTIONS: [&str; 25] = [
+    FUNC1, FUNC2, FUNC3, FUNC4, FUNC5, FUNC6, FUNC7, FUNC8, FUNC9, FUNC10, FUNC11, FUNC12, FUNC13,
+    FUNC14, FUNC15, FUNC16, FUNC17, FUNC18, FUNC19, FUNC20, FUNC21, FUNC22, FUNC23, FUNC24, FUNC25,
+];
 pub const AUTO_UPDATE: &str = "u";
 pub const PC: &str = "pc";
 pub const PRIV: &str = "priv";
@@ -1,12 +1,37 @@
 use super::*;
 
+/// What can be completed at a node of the parse tree. The attributes of a statement
+/// are completed by the variant named after it, ordered by relevance: the attributes
+/// most files set come first, and each alias right after its canonical spelling.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum LabelCompletion {
     None,
     Statement,
     Attribute,
+    Operand,
+    OperandStorage,
+    OperandElementType,
+    VectorOperandElementType,
+    CpuAttribute,
+    ConfigAttribute,
+    AcrAttribute,
+    AcmAttribute,
+    AcpAttribute,
+    InstructionAttribute,
+    VectorInstructionAttribute,
+    BackgroundInstructionAttribute,
+    UtilityInstructionAttribute,
+    TestSequenceAttribute,
+    TestbenchSequenceAttribute,
+    SequenceItem,
+    InsnGroupAttribute,
+    SyncAttribute,
+    StatusAttribute,
+    ResourceAttribute,
+    AclMiscellaneousSettingAttribute,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for LabelCompletion {
     fn default() -> Self {
         LabelCompletion::None
@@ -19,6 +44,39 @@ impl LabelCompletion {
             LabelCompletion::None => vec![],
             LabelCompletion::Statement => self.complete_statement(),
             LabelCompletion::Attribute => self.complete_attribute(),
+            LabelCompletion::Operand => self.complete_operand(),
+            LabelCompletion::OperandStorage => self.complete_operand_storage(),
+            LabelCompletion::OperandElementType => self.complete_operand_element_type(),
+            LabelCompletion::VectorOperandElementType => {
+                self.complete_vector_operand_element_type()
+            }
+            LabelCompletion::CpuAttribute => self.complete_cpu_attribute(),
+            LabelCompletion::ConfigAttribute => self.complete_config_attribute(),
+            LabelCompletion::AcrAttribute => self.complete_acr_attribute(),
+            LabelCompletion::AcmAttribute => self.complete_acm_attribute(),
+            LabelCompletion::AcpAttribute => self.complete_acp_attribute(),
+            LabelCompletion::InstructionAttribute => self.complete_instruction_attribute(),
+            LabelCompletion::VectorInstructionAttribute => {
+                self.complete_vector_instruction_attribute()
+            }
+            LabelCompletion::BackgroundInstructionAttribute => {
+                self.complete_background_instruction_attribute()
+            }
+            LabelCompletion::UtilityInstructionAttribute => {
+                self.complete_utility_instruction_attribute()
+            }
+            LabelCompletion::TestSequenceAttribute => self.complete_test_sequence_attribute(),
+            LabelCompletion::TestbenchSequenceAttribute => {
+                self.complete_testbench_sequence_attribute()
+            }
+            LabelCompletion::SequenceItem => self.complete_sequence_item(),
+            LabelCompletion::InsnGroupAttribute => self.complete_insn_group_attribute(),
+            LabelCompletion::SyncAttribute => self.complete_sync_attribute(),
+            LabelCompletion::StatusAttribute => self.complete_status_attribute(),
+            LabelCompletion::ResourceAttribute => self.complete_resource_attribute(),
+            LabelCompletion::AclMiscellaneousSettingAttribute => {
+                self.complete_acl_miscellaneous_setting_attribute()
+            }
         }
     }
 
@@ -29,7 +87,6 @@ impl LabelCompletion {
             snippet::statement::ACR,
             snippet::statement::ACM,
             literal::statement::ACP,
-            literal::statement::CSIM,
             literal::statement::CSIM_HEADER,
             literal::statement::INSTRUCTION_ALIAS[0],
             literal::statement::INSTRUCTION_ALIAS[1],
@@ -52,9 +109,253 @@ impl LabelCompletion {
             literal::statement::INSN_GROUP,
             literal::statement::SYNC,
             literal::statement::STATUS,
+            snippet::statement::INCLUDE,
         ]
     }
 
+    fn complete_operand(&self) -> Vec<&'static str> {
+        [
+            &[literal::token::IN, literal::token::OUT, literal::token::IO][..],
+            &literal::token::IMMEDIATES[..],
+            &literal::token::FUNCTIONS[..],
+        ]
+        .concat()
+    }
+
+    fn complete_operand_storage(&self) -> Vec<&'static str> {
+        [
+            &literal::token::XRF_ALIAS[..],
+            &literal::token::FRF_ALIAS[..],
+            &literal::token::VRF_ALIAS[..],
+            &literal::token::MEMORIES[..],
+        ]
+        .concat()
+    }
+
+    fn complete_operand_element_type(&self) -> Vec<&'static str> {
+        vec![
+            literal::token::UINT,
+            literal::token::INT,
+            literal::token::FP,
+            literal::token::MASK,
+        ]
+    }
+
+    fn complete_vector_operand_element_type(&self) -> Vec<&'static str> {
+        vec![
+            literal::token::UINT,
+            literal::token::INT,
+            literal::token::FP,
+            literal::token::DOUBLE_WIDTH_UINT,
+            literal::token::DOUBLE_WIDTH_INT,
+            literal::token::DOUBLE_WIDTH_FP,
+            literal::token::MASK,
+        ]
+    }
+
+    fn complete_cpu_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::NAME,
+            literal::attribute::VLEN,
+            literal::attribute::DLEN,
+            literal::attribute::ELEN,
+            literal::attribute::FLEN,
+            literal::attribute::FELEN,
+            literal::attribute::STREAMING_PORT_WIDTH,
+            literal::attribute::ADDRESS_BITS,
+            literal::attribute::ENDIAN,
+            literal::attribute::MARCH,
+        ]
+    }
+
+    fn complete_config_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::TIMEOUT_CYCLE,
+            literal::attribute::OPTIMIZATION_POLICY_ALIASES[0],
+            literal::attribute::OPTIMIZATION_POLICY_ALIASES[1],
+            literal::attribute::LM_LATENCY,
+            literal::attribute::BUS_LATENCY,
+            literal::attribute::RF_BUFFER_ALIASES[0],
+            literal::attribute::RF_BUFFER_ALIASES[1],
+            literal::attribute::GPR_BUFFER,
+            literal::attribute::FRF_BUFFER,
+            literal::attribute::INSN_QUEUE,
+            literal::attribute::GROUP_IN_BUFFER,
+            literal::attribute::GROUP_OUT_BUFFER,
+            literal::attribute::CUSTOM_ERROR_EN,
+            literal::attribute::CUSTOM_ERROR_BITS,
+            literal::attribute::ERROR_PC,
+            literal::attribute::CLOCK_DOMAIN_CROSSING_STAGE,
+            literal::attribute::EXPORT_LEVEL_AHB,
+            literal::attribute::EXPORT_LEVEL_AXI,
+            literal::attribute::EXPORT_LEVEL_SRAM,
+            literal::attribute::EXPORT_LEVEL_PORT,
+            literal::attribute::EXPORT_LEVEL_STREAMING_PORT,
+        ]
+    }
+
+    fn complete_acr_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::WIDTH,
+            literal::attribute::NUMBER_ALIASES[0],
+            literal::attribute::NUMBER_ALIASES[1],
+            literal::attribute::RESET,
+            literal::attribute::RESET_DEFAULT,
+        ]
+    }
+
+    fn complete_acm_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::WIDTH,
+            literal::attribute::NUMBER_ALIASES[0],
+            literal::attribute::NUMBER_ALIASES[1],
+            literal::attribute::INTERFACE,
+            literal::attribute::LATENCY,
+            literal::attribute::CONTENT,
+            literal::attribute::CONTENT_DEFAULT,
+            literal::attribute::BYTE_ENABLE_ALIASES[0],
+            literal::attribute::BYTE_ENABLE_ALIASES[1],
+            literal::attribute::WRITE_STROBE,
+            literal::attribute::MAX_BURST_LENGTH,
+            literal::attribute::ERROR_DETECT,
+        ]
+    }
+
+    fn complete_acp_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::IO_TYPE,
+            literal::attribute::WIDTH,
+            literal::attribute::INTERFACE,
+            literal::attribute::ACCESS_TYPE,
+            literal::attribute::BYTE_ENABLE_ALIASES[0],
+            literal::attribute::BYTE_ENABLE_ALIASES[1],
+            literal::attribute::MAX_BURST_LENGTH,
+            literal::attribute::EXPORT_LEVEL,
+        ]
+    }
+
+    fn complete_instruction_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::OPERAND_ALIASES[0],
+            literal::attribute::OPERAND_ALIASES[1],
+            literal::attribute::INSN_ENCODE,
+            literal::attribute::LATENCY,
+            literal::attribute::CSIM,
+            literal::attribute::CHISEL,
+            literal::attribute::SPINALHDL,
+            literal::attribute::IMPLIED_OPERAND_ALIASES[0],
+            literal::attribute::IMPLIED_OPERAND_ALIASES[1],
+            literal::attribute::CSR_OPERAND_ALIASES[0],
+            literal::attribute::CSR_OPERAND_ALIASES[1],
+            literal::attribute::BLOCKING,
+            literal::attribute::SIDE_EFFECT,
+        ]
+    }
+
+    fn complete_vector_instruction_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::OPERAND_ALIASES[0],
+            literal::attribute::OPERAND_ALIASES[1],
+            literal::attribute::INSN_ENCODE,
+            literal::attribute::VECTOR_UNIT,
+            literal::attribute::VECTOR_MASK,
+            literal::attribute::LATENCY,
+            literal::attribute::CSIM,
+            literal::attribute::CHISEL,
+            literal::attribute::SPINALHDL,
+            literal::attribute::IMPLIED_OPERAND_ALIASES[0],
+            literal::attribute::IMPLIED_OPERAND_ALIASES[1],
+            literal::attribute::CSR_OPERAND_ALIASES[0],
+            literal::attribute::CSR_OPERAND_ALIASES[1],
+            literal::attribute::BLOCKING,
+            literal::attribute::SIDE_EFFECT,
+            literal::attribute::RVV_CUSTOM_KILL,
+        ]
+    }
+
+    fn complete_background_instruction_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::OPERAND_ALIASES[0],
+            literal::attribute::OPERAND_ALIASES[1],
+            literal::attribute::IMPLIED_OPERAND_ALIASES[0],
+            literal::attribute::IMPLIED_OPERAND_ALIASES[1],
+            literal::attribute::CSR_OPERAND_ALIASES[0],
+            literal::attribute::CSR_OPERAND_ALIASES[1],
+            literal::attribute::INSN_ENCODE,
+            literal::attribute::LATENCY,
+            literal::attribute::SIDE_EFFECT,
+            literal::attribute::CSIM,
+            literal::attribute::CHISEL,
+            literal::attribute::SPINALHDL,
+            literal::attribute::OUTSTANDING_INSN_NUM,
+            literal::attribute::INTERRUPT,
+        ]
+    }
+
+    fn complete_utility_instruction_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::OPERAND_ALIASES[0],
+            literal::attribute::OPERAND_ALIASES[1],
+            literal::attribute::IMPLIED_OPERAND_ALIASES[0],
+            literal::attribute::IMPLIED_OPERAND_ALIASES[1],
+            literal::attribute::INSN_ENCODE,
+            literal::attribute::LATENCY,
+            literal::attribute::CSIM,
+            literal::attribute::CHISEL,
+            literal::attribute::SPINALHDL,
+            literal::attribute::UTILITY_KIND,
+            literal::attribute::UTILITY_ALIASES[0],
+            literal::attribute::UTILITY_ALIASES[1],
+        ]
+    }
+
+    fn complete_test_sequence_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::SEQUENCE_ALIASES[0],
+            literal::attribute::SEQUENCE_ALIASES[1],
+            literal::attribute::LOOP_TYPE,
+            literal::attribute::STRIDE,
+            literal::attribute::TEST_SEQUENCE_INIT[0],
+            literal::attribute::TEST_SEQUENCE_INIT[1],
+        ]
+    }
+
+    fn complete_testbench_sequence_attribute(&self) -> Vec<&'static str> {
+        vec![
+            literal::attribute::LIST,
+            literal::attribute::LOOP_TYPE,
+            literal::attribute::STRIDE,
+        ]
+    }
+
+    fn complete_sequence_item(&self) -> Vec<&'static str> {
+        vec![
+            literal::token::ANY_INSN,
+            literal::token::BG_SYNC,
+            literal::token::SYNC,
+        ]
+    }
+
+    fn complete_insn_group_attribute(&self) -> Vec<&'static str> {
+        vec![literal::attribute::LIST]
+    }
+
+    fn complete_sync_attribute(&self) -> Vec<&'static str> {
+        vec![literal::token::NBSYNC, literal::token::BSYNC]
+    }
+
+    fn complete_status_attribute(&self) -> Vec<&'static str> {
+        vec![literal::token::ST_STATUS, literal::token::ERROR_RD]
+    }
+
+    fn complete_resource_attribute(&self) -> Vec<&'static str> {
+        vec![literal::token::SHARED, literal::token::HART_ID]
+    }
+
+    fn complete_acl_miscellaneous_setting_attribute(&self) -> Vec<&'static str> {
+        vec![literal::attribute::MISC_SETTING]
+    }
+
     fn complete_attribute(&self) -> Vec<&'static str> {
         vec![
             literal::attribute::NAME,
@@ -160,3 +461,37 @@ impl LabelCompletion {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the first word of a statement completion, with the first choice of a
+    /// snippet choice placeholder like `${1|rom,ram|}`.
+    fn keyword(completion: &str) -> &str {
+        let completion = completion.strip_prefix("${1|").unwrap_or(completion);
+        completion.split([' ', ',']).next().unwrap()
+    }
+
+    #[test]
+    fn every_statement_completion_parses() {
+        for completion in LabelCompletion::Statement.completion() {
+            let keyword = keyword(completion);
+            let parses = [
+                format!("{keyword} {{\n}};\n"),
+                format!("{keyword} x {{\n}};\n"),
+                format!("{keyword} %{{ %}};\n"),
+                format!("{keyword} x %{{ %}};\n"),
+                format!("{keyword} \"x.ace\"\n"),
+            ]
+            .iter()
+            .any(|source| {
+                let ace = grammar::ace(source);
+                ace.errors().is_empty()
+                    && matches!(ace.statements().as_slice(), [statement]
+                        if !matches!(statement, grammar::Statement::Error(_)))
+            });
+            assert!(parses, "`{completion}` does not start a statement");
+        }
+    }
+}
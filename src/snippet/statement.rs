@@ -4,7 +4,6 @@
     //     Statement::acr,
     //     Statement::acm,
     //     Statement::acp,
-    //     Statement::csim,
     //     Statement::csim_header,
     //     Statement::instruction,
     //     Statement::vector_instruction,
@@ -21,4 +20,5 @@
 pub const CPU: &str = "cpu {\n    ${1}\n};\n";
 pub const CONFIG: &str = "config {\n    ${1}\n};\n";
 pub const ACR: &str = "reg ${1} {\n    ${2}\n};\n";
-pub const ACM: &str = "${1|rom,ram|} ${2} {\n    ${3}\n};\n";
\ No newline at end of file
+pub const ACM: &str = "${1|rom,ram|} ${2} {\n    ${3}\n};\n";
+pub const INCLUDE: &str = "#include \"${1}\"\n";
@@ -0,0 +1,572 @@
+//! The files of an **ACE** project, which are the root file and the ones it includes
+//! with `#include` directives, directly or not.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use getset::Getters;
+use self_cell::self_cell;
+use thiserror::Error;
+
+use crate::grammar::{ace, recovery, Ace, ParseTree, Query, Range, Statement};
+
+/// The file system the included files are loaded from, which lets tools and tests
+/// provide files that are not on the disk.
+pub trait FileSystem {
+    /// Returns whether `path` is a file.
+    fn is_file(&self, path: &Path) -> bool;
+    /// Returns the content of the file at `path`.
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+}
+
+/// The file system of the operating system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+}
+
+/// A file system holding its files in memory.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFileSystem {
+    files: HashMap<PathBuf, String>,
+}
+
+impl MemoryFileSystem {
+    /// Adds the file at `path` with `content`, replacing the previous one if any.
+    pub fn insert(&mut self, path: impl AsRef<Path>, content: impl Into<String>) -> &mut Self {
+        self.files.insert(normalize(path.as_ref()), content.into());
+        self
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(&normalize(path))
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.files
+            .get(&normalize(path))
+            .cloned()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+}
+
+/// The identifier of a file of a [`Workspace`], which is its index in [`Workspace::files`].
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct FileId(usize);
+
+/// A [`Range`] in a file of a [`Workspace`].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct FileRange {
+    pub file: FileId,
+    pub range: Range,
+}
+
+self_cell!(
+    /// The source of a file and its parse tree, which borrows from it.
+    struct Parsed {
+        owner: String,
+        #[covariant]
+        dependent: Ace,
+    }
+
+    impl {Debug}
+);
+
+/// A file of a [`Workspace`], with its parse tree, which is built once when the file
+/// is loaded.
+#[derive(Debug, Getters)]
+pub struct SourceFile {
+    #[getset(get = "pub")]
+    id: FileId,
+    #[getset(get = "pub")]
+    path: PathBuf,
+    parsed: Parsed,
+    /// The files included by this file, in the order of their directives.
+    #[getset(get = "pub")]
+    includes: Vec<FileId>,
+}
+
+impl SourceFile {
+    pub fn source(&self) -> &str {
+        self.parsed.borrow_owner()
+    }
+
+    /// Returns the parse tree of this file.
+    pub fn ace(&self) -> &Ace<'_> {
+        self.parsed.borrow_dependent()
+    }
+
+    pub fn errors(&self) -> &Vec<recovery::SyntaxError> {
+        self.ace().errors()
+    }
+
+    pub fn warnings(&self) -> &Vec<recovery::SyntaxError> {
+        self.ace().warnings()
+    }
+
+    /// Queries the parse tree of this file at the zero-based `line` and `character`
+    /// offset, see [`Ace::query`].
+    pub fn query(&self, line: u32, character: u32) -> FileQuery<'_> {
+        FileQuery {
+            file: self.id,
+            query: self.ace().query(line, character),
+        }
+    }
+}
+
+/// A [`Query`] of a [`SourceFile`], whose nodes are paired with the file.
+#[derive(Debug)]
+pub struct FileQuery<'b> {
+    file: FileId,
+    query: Query<'b>,
+}
+
+impl<'b> FileQuery<'b> {
+    /// Returns the leaf node found at the queried position.
+    pub fn node(&self) -> Option<FileNode<'b>> {
+        self.query.node().map(|node| FileNode {
+            file: self.file,
+            node,
+        })
+    }
+
+    /// Returns the ancestors of the found node, from the top-level statement down to
+    /// the parent of the node.
+    pub fn ancestors(&self) -> Vec<FileNode<'b>> {
+        self.query
+            .ancestors()
+            .iter()
+            .map(|&node| FileNode {
+                file: self.file,
+                node,
+            })
+            .collect()
+    }
+
+    /// Returns the completions at the queried position, see [`Query::show_completions`].
+    pub fn show_completions(&self) -> Vec<&'static str> {
+        self.query.show_completions()
+    }
+}
+
+/// A node of the parse tree of a [`SourceFile`], paired with the file.
+#[derive(Debug, Clone, Copy)]
+pub struct FileNode<'b> {
+    file: FileId,
+    node: &'b dyn ParseTree,
+}
+
+impl<'b> FileNode<'b> {
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    pub fn node(&self) -> &'b dyn ParseTree {
+        self.node
+    }
+
+    /// Returns the range of the node in the workspace.
+    pub fn range(&self) -> FileRange {
+        FileRange {
+            file: self.file,
+            range: self.node.range(),
+        }
+    }
+
+    /// Returns the child nodes in the order of their position.
+    pub fn children(&self) -> Vec<FileNode<'b>> {
+        self.node
+            .children()
+            .into_iter()
+            .map(|node| FileNode {
+                file: self.file,
+                node,
+            })
+            .collect()
+    }
+}
+
+/// An error of an `#include` directive, located at the path of the directive.
+#[derive(Debug, Error, Getters)]
+#[error("{kind}")]
+pub struct IncludeError {
+    #[getset(get = "pub")]
+    location: FileRange,
+    #[getset(get = "pub")]
+    kind: IncludeErrorKind,
+}
+
+/// The kind of an [`IncludeError`].
+#[derive(Debug, Error)]
+pub enum IncludeErrorKind {
+    /// The included file is found neither next to the including file nor in the
+    /// search paths.
+    #[error("cannot find the included file `{0}`")]
+    NotFound(String),
+    /// The included file cannot be read.
+    #[error("cannot read the included file `{}`", .0.display())]
+    Io(PathBuf, #[source] io::Error),
+    /// The included file includes the including file, directly or not. The cycle
+    /// starts and ends with the included file.
+    #[error("the included file `{}` includes itself", .0.display())]
+    Cycle(PathBuf, Vec<FileId>),
+}
+
+/// The root file of a project and the files it includes, with the graph of their
+/// `#include` directives.
+#[derive(Debug, Getters)]
+pub struct Workspace {
+    /// The files, the first one being the root file.
+    #[getset(get = "pub")]
+    files: Vec<SourceFile>,
+    /// The errors of the `#include` directives.
+    #[getset(get = "pub")]
+    errors: Vec<IncludeError>,
+}
+
+impl Workspace {
+    /// Returns the root file.
+    pub fn root(&self) -> &SourceFile {
+        &self.files[0]
+    }
+
+    /// Returns the file of `id`.
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        &self.files[id.0]
+    }
+
+    /// Returns the files with their identifiers.
+    pub fn iter(&self) -> impl Iterator<Item = (FileId, &SourceFile)> {
+        self.files.iter().map(|file| (file.id, file))
+    }
+
+    /// Returns the identifier of the file at `path`, if it is in the workspace.
+    pub fn find(&self, path: impl AsRef<Path>) -> Option<FileId> {
+        let path = normalize(path.as_ref());
+        self.files
+            .iter()
+            .position(|file| file.path == path)
+            .map(FileId)
+    }
+
+    /// Returns the syntax errors of every file, with their range in the workspace.
+    pub fn syntax_errors(&self) -> impl Iterator<Item = (FileRange, &recovery::SyntaxError)> {
+        self.files.iter().flat_map(|file| {
+            file.errors().iter().map(|error| {
+                let range = FileRange {
+                    file: file.id,
+                    range: *error.range(),
+                };
+                (range, error)
+            })
+        })
+    }
+}
+
+/// Loads the files of a [`Workspace`] from a [`FileSystem`].
+///
+/// The path of an `#include` directive is resolved relative to the directory of the
+/// including file first, and then relative to each of the search paths in order.
+///
+/// ```
+/// # use ace_parser::workspace::{IncludeErrorKind, MemoryFileSystem, Resolver};
+/// let mut fs = MemoryFileSystem::default();
+/// fs.insert("/project/main.ace", "#include \"cpu.ace\"\n#include \"common.ace\"\n")
+///     .insert("/project/cpu.ace", "cpu {\n    vlen = 512;\n};\n")
+///     .insert("/lib/common.ace", "#include \"/project/main.ace\"\n");
+///
+/// let workspace = Resolver::new(fs).search_path("/lib").resolve("/project/main.ace").unwrap();
+/// assert_eq!(workspace.files().len(), 3);
+/// assert_eq!(workspace.root().includes().len(), 2);
+/// assert!(matches!(workspace.errors()[0].kind(), IncludeErrorKind::Cycle(..)));
+/// ```
+#[derive(Debug)]
+pub struct Resolver<F> {
+    fs: F,
+    search_paths: Vec<PathBuf>,
+}
+
+impl<F: FileSystem> Resolver<F> {
+    /// Creates a resolver loading the files from `fs`, without any search path.
+    pub fn new(fs: F) -> Self {
+        Resolver {
+            fs,
+            search_paths: vec![],
+        }
+    }
+
+    /// Appends `path` to the search paths of the included files.
+    pub fn search_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.search_paths.push(path.into());
+        self
+    }
+
+    /// Loads the file at `root` and every file it includes, directly or not. The
+    /// included files which cannot be loaded are reported in [`Workspace::errors`].
+    pub fn resolve(&self, root: impl AsRef<Path>) -> io::Result<Workspace> {
+        let root = normalize(root.as_ref());
+        let source = self.fs.read_to_string(&root)?;
+        let mut workspace = Workspace {
+            files: vec![],
+            errors: vec![],
+        };
+        self.load(&mut workspace, root, source, &mut vec![]);
+        Ok(workspace)
+    }
+
+    /// Parses the file at `path` with `source`, adds it to `workspace`, and loads the
+    /// files it includes. `stack` holds the files including it, from the root file
+    /// down. Returns the identifier of the added file.
+    fn load(
+        &self,
+        workspace: &mut Workspace,
+        path: PathBuf,
+        source: String,
+        stack: &mut Vec<FileId>,
+    ) -> FileId {
+        let id = FileId(workspace.files.len());
+        let parsed = Parsed::new(source, |source| ace(source));
+        let directives: Vec<_> = parsed
+            .borrow_dependent()
+            .statements()
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::Include(include) => {
+                    Some((include.path().value().into_owned(), include.path().range()))
+                }
+                _ => None,
+            })
+            .collect();
+        let directory = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        workspace.files.push(SourceFile {
+            id,
+            path,
+            parsed,
+            includes: vec![],
+        });
+        stack.push(id);
+        self.load_includes(workspace, &directory, directives, stack);
+        stack.pop();
+        id
+    }
+
+    /// Loads the files included by the `directives` of the last file of `stack`, which
+    /// is in `directory`.
+    fn load_includes(
+        &self,
+        workspace: &mut Workspace,
+        directory: &Path,
+        directives: Vec<(String, Range)>,
+        stack: &mut Vec<FileId>,
+    ) {
+        let id = *stack.last().expect("the stack holds the including file");
+        for (path, range) in directives {
+            let location = FileRange { file: id, range };
+            let Some(resolved) = self.find(directory, &path) else {
+                workspace.errors.push(IncludeError {
+                    location,
+                    kind: IncludeErrorKind::NotFound(path),
+                });
+                continue;
+            };
+            if let Some(included) = workspace.find(&resolved) {
+                if let Some(start) = stack.iter().position(|file| *file == included) {
+                    let mut cycle = stack[start..].to_vec();
+                    cycle.push(included);
+                    workspace.errors.push(IncludeError {
+                        location,
+                        kind: IncludeErrorKind::Cycle(resolved, cycle),
+                    });
+                } else {
+                    workspace.files[id.0].includes.push(included);
+                }
+                continue;
+            }
+            match self.fs.read_to_string(&resolved) {
+                Ok(source) => {
+                    let included = self.load(workspace, resolved, source, stack);
+                    workspace.files[id.0].includes.push(included);
+                }
+                Err(error) => workspace.errors.push(IncludeError {
+                    location,
+                    kind: IncludeErrorKind::Io(resolved, error),
+                }),
+            }
+        }
+    }
+
+    /// Returns the path of the file included as `path` by a file of `directory`.
+    fn find(&self, directory: &Path, path: &str) -> Option<PathBuf> {
+        std::iter::once(directory)
+            .chain(self.search_paths.iter().map(PathBuf::as_path))
+            .map(|base| normalize(&base.join(path)))
+            .find(|candidate| self.fs.is_file(candidate))
+    }
+}
+
+/// Returns `path` without its `.` components and with its `..` components applied,
+/// so that the paths of a file are equal however it is included.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::{NodeKind, Position};
+
+    fn resolve(fs: &MemoryFileSystem) -> Workspace {
+        Resolver::new(fs.clone())
+            .search_path("/lib")
+            .search_path("/vendor")
+            .resolve("/project/main.ace")
+            .unwrap()
+    }
+
+    fn paths(workspace: &Workspace, ids: &[FileId]) -> Vec<PathBuf> {
+        ids.iter()
+            .map(|id| workspace.file(*id).path().clone())
+            .collect()
+    }
+
+    #[test]
+    fn missing_include_is_not_found() {
+        let mut fs = MemoryFileSystem::default();
+        fs.insert("/project/main.ace", "#include \"missing.ace\"\n");
+        let workspace = resolve(&fs);
+        assert_eq!(workspace.files().len(), 1);
+        let [error] = workspace.errors().as_slice() else {
+            panic!("expected one error, found {:?}", workspace.errors());
+        };
+        assert!(matches!(error.kind(), IncludeErrorKind::NotFound(path) if path == "missing.ace"));
+        assert_eq!(error.location().file, workspace.root().id);
+        assert_eq!(error.location().range.start, Position::new(0, 9));
+
+        let error = Resolver::new(fs).resolve("/project/other.ace").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn including_directory_comes_before_the_search_paths_in_order() {
+        let mut fs = MemoryFileSystem::default();
+        fs.insert(
+            "/project/main.ace",
+            "#include \"a.ace\"\n#include \"b.ace\"\n",
+        )
+        .insert("/project/a.ace", "")
+        .insert("/lib/a.ace", "")
+        .insert("/lib/b.ace", "")
+        .insert("/vendor/b.ace", "");
+        let workspace = resolve(&fs);
+        assert!(workspace.errors().is_empty());
+        assert_eq!(
+            paths(&workspace, workspace.root().includes()),
+            [Path::new("/project/a.ace"), Path::new("/lib/b.ace")]
+        );
+    }
+
+    #[test]
+    fn diamond_include_is_loaded_once() {
+        let mut fs = MemoryFileSystem::default();
+        fs.insert(
+            "/project/main.ace",
+            "#include \"a.ace\"\n#include \"b.ace\"\n",
+        )
+        .insert("/project/a.ace", "#include \"common.ace\"\n")
+        .insert("/project/b.ace", "#include \"common.ace\"\n")
+        .insert("/project/common.ace", "cpu {\n    vlen = 512;\n};\n");
+        let workspace = resolve(&fs);
+        assert!(workspace.errors().is_empty());
+        assert_eq!(workspace.files().len(), 4);
+        let common = workspace.find("/project/common.ace").unwrap();
+        for file in ["/project/a.ace", "/project/b.ace"] {
+            let file = workspace.file(workspace.find(file).unwrap());
+            assert_eq!(file.includes(), &[common]);
+        }
+    }
+
+    #[test]
+    fn paths_are_normalized() {
+        let mut fs = MemoryFileSystem::default();
+        fs.insert(
+            "/project/./main.ace",
+            "#include \"../project/cpu/../cpu.ace\"\n#include \"./cpu.ace\"\n",
+        )
+        .insert("/project/cpu.ace", "");
+        let workspace = resolve(&fs);
+        assert!(workspace.errors().is_empty());
+        assert_eq!(workspace.files().len(), 2);
+        let cpu = workspace.find("/project/sub/../cpu.ace").unwrap();
+        assert_eq!(workspace.file(cpu).path(), Path::new("/project/cpu.ace"));
+        assert_eq!(workspace.root().includes(), &[cpu, cpu]);
+    }
+
+    #[test]
+    fn cycle_through_several_files_is_reported() {
+        let mut fs = MemoryFileSystem::default();
+        fs.insert("/project/main.ace", "#include \"a.ace\"\n")
+            .insert("/project/a.ace", "#include \"b.ace\"\n")
+            .insert("/project/b.ace", "#include \"c.ace\"\n")
+            .insert("/project/c.ace", "#include \"a.ace\"\n");
+        let workspace = resolve(&fs);
+        assert_eq!(workspace.files().len(), 4);
+        let [error] = workspace.errors().as_slice() else {
+            panic!("expected one error, found {:?}", workspace.errors());
+        };
+        let IncludeErrorKind::Cycle(path, cycle) = error.kind() else {
+            panic!("expected a cycle, found {error:?}");
+        };
+        assert_eq!(path, Path::new("/project/a.ace"));
+        assert_eq!(
+            paths(&workspace, cycle),
+            ["a", "b", "c", "a"].map(|name| PathBuf::from(format!("/project/{name}.ace")))
+        );
+        assert_eq!(
+            error.location().file,
+            workspace.find("/project/c.ace").unwrap()
+        );
+    }
+
+    #[test]
+    fn syntax_errors_are_located_in_their_file() {
+        let mut fs = MemoryFileSystem::default();
+        fs.insert("/project/main.ace", "#include \"cpu.ace\"\n")
+            .insert("/project/cpu.ace", "cpu {\n    vlen = 512\n};\n");
+        let workspace = resolve(&fs);
+        let cpu = workspace.find("/project/cpu.ace").unwrap();
+        let errors: Vec<_> = workspace.syntax_errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0.file, cpu);
+        assert_eq!(errors[0].0.range, *errors[0].1.range());
+
+        let query = workspace.file(cpu).query(1, 5);
+        let node = query.node().unwrap();
+        assert_eq!(node.node().node_kind(), NodeKind::Token("vlen"));
+        assert_eq!(node.range().file, cpu);
+        assert!(query
+            .ancestors()
+            .iter()
+            .all(|ancestor| ancestor.file() == cpu));
+    }
+}
@@ -0,0 +1,169 @@
+//! Drives the `ace-lsp` binary over stdio with a scripted JSON-RPC client.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use serde_json::{json, Value};
+
+const URI: &str = "file:///project/main.ace";
+
+/// A client speaking JSON-RPC with the language server it spawns.
+struct Client {
+    server: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Client {
+    fn spawn() -> Self {
+        let mut server = Command::new(env!("CARGO_BIN_EXE_ace-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("the server should start");
+        let stdin = server.stdin.take().unwrap();
+        let stdout = BufReader::new(server.stdout.take().unwrap());
+        Client {
+            server,
+            stdin,
+            stdout,
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn request(&mut self, id: i64, method: &str, params: Value) -> Value {
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        self.receive(|message| message["id"] == id)
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Returns the next message accepted by `filter`, skipping the other ones.
+    fn receive(&mut self, filter: impl Fn(&Value) -> bool) -> Value {
+        loop {
+            let mut length = 0;
+            loop {
+                let mut header = String::new();
+                self.stdout.read_line(&mut header).unwrap();
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                if let Some(value) = header.strip_prefix("Content-Length: ") {
+                    length = value.parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            self.stdout.read_exact(&mut body).unwrap();
+            let message: Value = serde_json::from_slice(&body).unwrap();
+            if filter(&message) {
+                return message;
+            }
+        }
+    }
+
+    fn diagnostics(&mut self) -> Vec<Value> {
+        let notification =
+            self.receive(|message| message["method"] == "textDocument/publishDiagnostics");
+        assert_eq!(notification["params"]["uri"], URI);
+        notification["params"]["diagnostics"]
+            .as_array()
+            .unwrap()
+            .clone()
+    }
+
+    fn completion(&mut self, id: i64, line: u32, character: u32) -> Value {
+        self.request(
+            id,
+            "textDocument/completion",
+            json!({
+                "textDocument": { "uri": URI },
+                "position": { "line": line, "character": character },
+            }),
+        )
+    }
+}
+
+#[test]
+fn serves_completions_and_diagnostics() {
+    let mut client = Client::spawn();
+
+    let initialize = client.request(1, "initialize", json!({ "capabilities": {} }));
+    let capabilities = &initialize["result"]["capabilities"];
+    assert!(capabilities["completionProvider"].is_object());
+    assert_eq!(capabilities["positionEncoding"], "utf-16");
+    client.notify("initialized", json!({}));
+
+    let text = "cpu {\n    vlen = 512;\n};\n";
+    client.notify(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": { "uri": URI, "languageId": "ace", "version": 1, "text": text },
+        }),
+    );
+    assert_eq!(client.diagnostics(), Vec::<Value>::new());
+
+    let completion = client.completion(2, 3, 0);
+    let items = completion["result"].as_array().unwrap();
+    let cpu = items
+        .iter()
+        .find(|item| item["label"] == "cpu")
+        .expect("the cpu statement should be completed");
+    assert_eq!(cpu["insertText"], "cpu {\n    ${1}\n};\n");
+    assert_eq!(cpu["insertTextFormat"], 2);
+    let ram = items
+        .iter()
+        .find(|item| item["label"] == "rom/ram")
+        .unwrap();
+    assert_eq!(ram["insertTextFormat"], 2);
+
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 2 },
+            "contentChanges": [{ "text": "cpu {\n    vlen = 512\n};\n" }],
+        }),
+    );
+    let diagnostics = client.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["severity"], 1);
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 1, "character": 14 })
+    );
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("expected `;` after `vlen = 512`"));
+
+    let shutdown = client.request(3, "shutdown", Value::Null);
+    assert_eq!(shutdown["result"], Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.server.wait().unwrap().success());
+}
+
+#[test]
+fn answers_malformed_params_with_an_error() {
+    let mut client = Client::spawn();
+    client.request(1, "initialize", json!({ "capabilities": {} }));
+    client.notify("initialized", json!({}));
+
+    client.notify("textDocument/didOpen", json!({ "textDocument": 42 }));
+    let response = client.request(2, "textDocument/completion", json!({ "position": "x" }));
+    assert_eq!(response["error"]["code"], -32602);
+
+    let completion = client.completion(3, 0, 0);
+    assert_eq!(completion["result"], Value::Null);
+
+    client.request(4, "shutdown", Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.server.wait().unwrap().success());
+}